@@ -0,0 +1,59 @@
+//! Benchmarks for the calculation hot path.
+//!
+//! Run with `cargo bench`. The 144000-day (one bak'tun) scan compares a
+//! serial loop against `calculate_date_range` when the `parallel` feature is on.
+
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, RwLock};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use mayan_calendar::calendar::{CalendarCache, CalendarData, ParallelCalendarCalculator};
+use mayan_calendar::mayan_date::MayanDate;
+use mayan_calendar::metrics::Metrics;
+
+const GMT: i32 = 584283;
+const BAKTUN_13: i32 = 1_872_000;
+
+fn calculator() -> ParallelCalendarCalculator {
+    let cache = Arc::new(RwLock::new(CalendarCache::new(NonZeroUsize::new(1024).unwrap())));
+    ParallelCalendarCalculator::new(cache, Arc::new(Metrics::new()), GMT)
+}
+
+fn single_date(c: &mut Criterion) {
+    c.bench_function("MayanDate::from_days", |b| b.iter(|| MayanDate::from_days(black_box(BAKTUN_13))));
+    c.bench_function("CalendarData::from_days", |b| {
+        b.iter(|| CalendarData::from_days(black_box(BAKTUN_13), GMT))
+    });
+
+    let calculator = calculator();
+    calculator.calculate_new_data(BAKTUN_13).unwrap();
+    c.bench_function("calculate_new_data (cache hit)", |b| {
+        b.iter(|| calculator.calculate_new_data(black_box(BAKTUN_13)))
+    });
+}
+
+fn baktun_scan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("144000 days");
+    group.sample_size(10);
+    let days = 144_000;
+    group.bench_with_input(BenchmarkId::new("serial", days), &days, |b, &days| {
+        b.iter(|| {
+            (BAKTUN_13..BAKTUN_13 + days)
+                .map(|day| CalendarData::from_days(day, GMT))
+                .collect::<Vec<_>>()
+        })
+    });
+    #[cfg(feature = "parallel")]
+    {
+        let calculator = calculator();
+        group.bench_with_input(BenchmarkId::new("calculate_date_range", days), &days, |b, &days| {
+            b.iter(|| calculator.calculate_date_range(BAKTUN_13..BAKTUN_13 + days))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, single_date, baktun_scan);
+criterion_main!(benches);
@@ -0,0 +1,77 @@
+//! Minimal JSON server for calendar lookups.
+//!
+//! It serves a single route:
+//!
+//! `GET /date/YYYY-MM-DD` returns the `CalendarData` for that day as JSON.
+//! A malformed date gets a 400; any other path or method gets a 404.
+//!
+//! Run it with `cargo run --example server --features server [ADDRESS]`.
+//! The address defaults to 127.0.0.1:8080. The correlation comes from the
+//! config file named by `MAYAN_CONFIG`, as for the binary.
+
+use chrono::NaiveDate;
+use tiny_http::{Header, Method, Response, Server};
+
+use mayan_calendar::calendar::CalendarData;
+use mayan_calendar::config::Config;
+
+// Status code and body for a request
+fn handle(method: &Method, url: &str, correlation: i32) -> (u16, String) {
+    let Some(date) = url.strip_prefix("/date/").filter(|_| *method == Method::Get) else {
+        return (404, "Not found\n".to_string());
+    };
+    let date = match date.parse::<NaiveDate>() {
+        Ok(date) => date,
+        Err(e) => return (400, format!("Invalid date '{}': {}\n", date, e)),
+    };
+    let data = CalendarData::new(date.and_hms_opt(0, 0, 0).unwrap(), correlation);
+    match serde_json::to_string(&data) {
+        Ok(json) => (200, json),
+        Err(e) => (500, format!("Failed to serialize calendar data: {}\n", e)),
+    }
+}
+
+fn main() {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    let address = std::env::args().nth(1).unwrap_or_else(|| "127.0.0.1:8080".to_string());
+    let server = Server::http(&address).unwrap_or_else(|e| {
+        eprintln!("Failed to listen on {}: {}", address, e);
+        std::process::exit(1);
+    });
+    let correlation = config.correlation.jdn_offset();
+    println!("Listening on http://{}/date/YYYY-MM-DD", address);
+
+    for request in server.incoming_requests() {
+        let (status, body) = handle(request.method(), request.url(), correlation);
+        let content_type = if status == 200 { "application/json" } else { "text/plain; charset=utf-8" };
+        let response = Response::from_string(body)
+            .with_status_code(status)
+            .with_header(Header::from_bytes("Content-Type", content_type).unwrap());
+        if let Err(e) = request.respond(response) {
+            eprintln!("Failed to send response: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_routes() {
+        let (status, body) = handle(&Method::Get, "/date/2012-12-21", 584283);
+        assert_eq!(status, 200);
+        let data: CalendarData = serde_json::from_str(&body).unwrap();
+        assert_eq!(data.mayan.long_count.to_string(), "13.0.0.0.0");
+
+        assert_eq!(handle(&Method::Get, "/date/2012-13-01", 584283).0, 400);
+        assert_eq!(handle(&Method::Get, "/today", 584283).0, 404);
+        assert_eq!(handle(&Method::Post, "/date/2012-12-21", 584283).0, 404);
+    }
+}
@@ -0,0 +1,633 @@
+use std::fmt;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::date_utils::{checked_gregorian, gregorian_to_jdn, haab_year_start, tzolkin_date, DateError, TzolkinDate};
+
+// First, let's define our astronomical constants
+lazy_static! {
+    static ref ASTRONOMICAL_CYCLES: HashMap<&'static str, f64> = {
+        let mut m = HashMap::new();
+        // Basic cycles
+        m.insert("synodic_month", 29.530588); // Average length of lunar month
+        m.insert("venus_synodic", 583.92);    // Venus synodic period
+        m.insert("solar_year", 365.242189);   // Tropical year length
+        m.insert("eclipse_year", 346.62);     // Time between similar eclipse conditions
+        
+        // Maya-specific cycles
+        m.insert("tzolkin_cycle", 260.0);     // Length of Tzolkin cycle
+        m.insert("haab_cycle", 365.0);        // Length of Haab cycle
+        m.insert("calendar_round", 18980.0);  // Least common multiple of Tzolkin and Haab
+        m.insert("long_count_cycle", 1872000.0); // Length of Long Count cycle (13 baktuns)
+        m
+    };
+}
+
+/// Mean equinox/solstice polynomials in Y from Meeus, Astronomical Algorithms ch. 27:
+/// (name, coefficients for years -1000..1000, coefficients for years 1000..3000)
+const SEASONAL_EVENTS: [(&str, [f64; 5], [f64; 5]); 4] = [
+    (
+        "Spring Equinox",
+        [1721139.29189, 365242.13740, 0.06134, 0.00111, -0.00071],
+        [2451623.80984, 365242.37404, 0.05169, -0.00411, -0.00057],
+    ),
+    (
+        "Summer Solstice",
+        [1721233.25401, 365241.72562, -0.05323, 0.00907, 0.00025],
+        [2451716.56767, 365241.62603, 0.00325, 0.00888, -0.00030],
+    ),
+    (
+        "Autumn Equinox",
+        [1721325.70455, 365242.49558, -0.11677, -0.00297, 0.00074],
+        [2451810.21715, 365242.01767, -0.11575, 0.00337, 0.00078],
+    ),
+    (
+        "Winter Solstice",
+        [1721414.39987, 365242.88257, -0.00769, -0.00933, -0.00006],
+        [2451900.05952, 365242.74049, -0.06223, -0.00823, 0.00032],
+    ),
+];
+
+/// Periodic terms (A, B, C) correcting the mean instants, Meeus table 27.C
+const SEASONAL_PERIODIC_TERMS: [(f64, f64, f64); 24] = [
+    (485.0, 324.96, 1934.136),
+    (203.0, 337.23, 32964.467),
+    (199.0, 342.08, 20.186),
+    (182.0, 27.85, 445267.112),
+    (156.0, 73.14, 45036.886),
+    (136.0, 171.52, 22518.443),
+    (77.0, 222.54, 65928.934),
+    (74.0, 296.72, 3034.906),
+    (70.0, 243.58, 9037.513),
+    (58.0, 119.81, 33718.147),
+    (52.0, 297.17, 150.678),
+    (50.0, 21.02, 2281.232),
+    (45.0, 247.54, 29929.562),
+    (44.0, 325.15, 31555.956),
+    (29.0, 60.93, 4443.417),
+    (18.0, 155.12, 67555.328),
+    (17.0, 288.79, 4562.452),
+    (16.0, 198.04, 62894.029),
+    (14.0, 199.76, 31436.921),
+    (12.0, 95.39, 14577.848),
+    (12.0, 287.11, 31931.756),
+    (12.0, 320.81, 34777.259),
+    (9.0, 227.73, 1222.114),
+    (8.0, 15.45, 16859.074),
+];
+
+/// Julian Date of a reference new moon (January 6, 2000, 14:24 UTC)
+const NEW_MOON_EPOCH: f64 = 2451550.1;
+
+/// Phase of the Moon, in eighths of the synodic month
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoonPhase {
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl fmt::Display for MoonPhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            MoonPhase::New => "🌑 New Moon",
+            MoonPhase::WaxingCrescent => "🌒 Waxing Crescent",
+            MoonPhase::FirstQuarter => "🌓 First Quarter",
+            MoonPhase::WaxingGibbous => "🌔 Waxing Gibbous",
+            MoonPhase::Full => "🌕 Full Moon",
+            MoonPhase::WaningGibbous => "🌖 Waning Gibbous",
+            MoonPhase::LastQuarter => "🌗 Last Quarter",
+            MoonPhase::WaningCrescent => "🌘 Waning Crescent",
+        })
+    }
+}
+
+/// Calculates the moon phase for a given Julian Day Number
+pub fn moon_phase(jdn: i32) -> MoonPhase {
+    // The lunar synodic month is approximately 29.53059 days
+    let lunar_month = ASTRONOMICAL_CYCLES["synodic_month"];
+
+    // Moon age in days since the last new moon, counted from the reference new moon.
+    // rem_euclid keeps the age positive for dates before the epoch.
+    let age = (jdn as f64 - NEW_MOON_EPOCH).rem_euclid(lunar_month);
+
+    // Calculate the phase angle (0 to 1, where 0 = new moon, 0.5 = full moon)
+    let phase = age / lunar_month;
+    
+    match phase {
+        p if p < 0.0625 => MoonPhase::New,
+        p if p < 0.1875 => MoonPhase::WaxingCrescent,
+        p if p < 0.3125 => MoonPhase::FirstQuarter,
+        p if p < 0.4375 => MoonPhase::WaxingGibbous,
+        p if p < 0.5625 => MoonPhase::Full,
+        p if p < 0.6875 => MoonPhase::WaningGibbous,
+        p if p < 0.8125 => MoonPhase::LastQuarter,
+        p if p < 0.9375 => MoonPhase::WaningCrescent,
+        _ => MoonPhase::New,
+    }
+}
+
+/// The reference new moon counted from 0.0.0.0.0 under GMT: 12.19.6.15.7
+const LUNAR_SERIES_EPOCH: f64 = NEW_MOON_EPOCH - 584283.0;
+
+/// The Lunar Series of an inscription: the moon's age in days (Glyphs D and E),
+/// the lunation's place in its group of six (Glyph C), and whether the month
+/// has 29 or 30 days (Glyph A)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LunarSeries {
+    pub age: u8,
+    pub lunation: u8,
+    pub month_length: u8,
+}
+
+/// Lunar Series for a day counted from 0.0.0.0.0. Lunations run from the day of
+/// one mean new moon to the next and are grouped in sixes from the reference
+/// new moon of January 6, 2000.
+pub fn lunar_series(days_since_creation: i32) -> LunarSeries {
+    let lunar_month = ASTRONOMICAL_CYCLES["synodic_month"];
+    // Day on which the k-th new moon after the reference one falls
+    let new_moon_day = |k: f64| (LUNAR_SERIES_EPOCH + k * lunar_month + 0.5).floor() as i32;
+
+    let mut k = ((days_since_creation as f64 + 0.5 - LUNAR_SERIES_EPOCH) / lunar_month).floor();
+    // Guard against rounding right at a new moon
+    if new_moon_day(k) > days_since_creation {
+        k -= 1.0;
+    } else if new_moon_day(k + 1.0) <= days_since_creation {
+        k += 1.0;
+    }
+    let start = new_moon_day(k);
+    LunarSeries {
+        age: (days_since_creation - start) as u8,
+        lunation: k.rem_euclid(6.0) as u8 + 1,
+        month_length: (new_moon_day(k + 1.0) - start) as u8,
+    }
+}
+
+/// Heliacal rising of Venus as morning star, four days after the
+/// June 3, 2020 inferior conjunction (JDN 2459008 = June 7, 2020)
+const VENUS_HELIACAL_RISE_EPOCH: f64 = 2459008.0;
+
+/// Where Venus is in its synodic cycle, as the Dresden Codex divides it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VenusPhase {
+    MorningStar,
+    SuperiorConjunction,
+    EveningStar,
+    InferiorConjunction,
+}
+
+impl fmt::Display for VenusPhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            VenusPhase::MorningStar => "🌅 Morning Star",
+            VenusPhase::SuperiorConjunction => "⭐ Superior Conjunction",
+            VenusPhase::EveningStar => "🌇 Evening Star",
+            VenusPhase::InferiorConjunction => "⭐ Inferior Conjunction",
+        })
+    }
+}
+
+/// Calculates the Venus phase for a given Julian Day Number, along with
+/// the day (0-583) within the current synodic cycle
+pub fn venus_phase(jdn: i32) -> (VenusPhase, i32) {
+    // Venus has a synodic period of approximately 583.92 days
+    let venus_period = ASTRONOMICAL_CYCLES["venus_synodic"];
+    
+    // Days since the most recent heliacal rising
+    let cycle_day = (jdn as f64 - VENUS_HELIACAL_RISE_EPOCH).rem_euclid(venus_period) as i32;
+    
+    // Station lengths follow the Dresden Codex Venus table:
+    // 236 days morning star, 90 invisible, 250 evening star, 8 invisible
+    let phase = match cycle_day {
+        d if d < 236 => VenusPhase::MorningStar,
+        d if d < 326 => VenusPhase::SuperiorConjunction,
+        d if d < 576 => VenusPhase::EveningStar,
+        _ => VenusPhase::InferiorConjunction,
+    };
+    (phase, cycle_day)
+}
+
+/// Year bearer systems, which named the year after the Tzolk'in day falling
+/// on a different day at the start of the Haab'
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum YearBearerSystem {
+    /// Ik', Manik', Eb', Kab'an on 0 Pop, as at Classic-period Tikal
+    #[default]
+    Classic,
+    /// Ak'b'al, Lamat, B'en, Etz'nab' on 1 Pop, as in the Puuc region
+    Puuc,
+    /// K'an, Muluk, Ix, Kawak on 2 Pop, as recorded by Landa in Yucatán
+    Yucatec,
+}
+
+impl YearBearerSystem {
+    fn haab_offset(self) -> i32 {
+        match self {
+            YearBearerSystem::Classic => 0,
+            YearBearerSystem::Puuc => 1,
+            YearBearerSystem::Yucatec => 2,
+        }
+    }
+}
+
+/// The Tzolk'in day bearing the Haab' year that contains `days` (counted from 0.0.0.0.0)
+pub fn year_bearer_date(days: i32, system: YearBearerSystem) -> TzolkinDate {
+    tzolkin_date(haab_year_start(days) + system.haab_offset())
+}
+
+/// Determines the Year Bearer for a day counted from 0.0.0.0.0, e.g. "1 Kab'an"
+pub fn year_bearer(days: i32) -> String {
+    year_bearer_date(days, YearBearerSystem::default()).to_string()
+}
+
+/// Julian Ephemeris Day of a year's equinox or solstice (0 = March .. 3 = December)
+fn seasonal_event_jde(year: i32, event: usize) -> f64 {
+    let (_, early, modern) = SEASONAL_EVENTS[event];
+    let (coefficients, y) = if year < 1000 {
+        (early, year as f64 / 1000.0)
+    } else {
+        (modern, (year as f64 - 2000.0) / 1000.0)
+    };
+    let jde0 = coefficients.iter().rev().fold(0.0, |acc, c| acc * y + c);
+
+    let t = (jde0 - 2451545.0) / 36525.0;
+    let w = (35999.373 * t - 2.47).to_radians();
+    let delta_lambda = 1.0 + 0.0334 * w.cos() + 0.0007 * (2.0 * w).cos();
+    let s: f64 = SEASONAL_PERIODIC_TERMS
+        .iter()
+        .map(|&(a, b, c)| a * (b + c * t).to_radians().cos())
+        .sum();
+    jde0 + 0.00001 * s / delta_lambda
+}
+
+/// Julian Day Number of the (UTC) day on which a year's equinox or solstice falls
+fn seasonal_event_jdn(year: i32, event: usize) -> i32 {
+    (seasonal_event_jde(year, event) + 0.5).floor() as i32
+}
+
+/// The next equinox or solstice after a Gregorian date, and the days until it.
+/// Fails on dates that don't exist, like month 13.
+pub fn next_solstice_or_equinox(year: i32, month: i32, day: i32) -> Result<(String, i32), DateError> {
+    checked_gregorian(year, month, day).map(next_seasonal_event)
+}
+
+/// The next equinox or solstice after `date`, and the days until it
+pub fn next_seasonal_event(date: NaiveDate) -> (String, i32) {
+    let year = date.year();
+    let current_jdn = gregorian_to_jdn(year, date.month() as i32, date.day() as i32);
+
+    // Find the next seasonal event later this year
+    for (event, &(event_name, _, _)) in SEASONAL_EVENTS.iter().enumerate() {
+        let event_jdn = seasonal_event_jdn(year, event);
+        if event_jdn > current_jdn {
+            return (event_name.to_string(), event_jdn - current_jdn);
+        }
+    }
+
+    // If we're past the winter solstice, return next year's spring equinox
+    let (event_name, _, _) = SEASONAL_EVENTS[0];
+    (event_name.to_string(), seasonal_event_jdn(year + 1, 0) - current_jdn)
+}
+
+/// Julian Date of the Sun's passage through a lunar node two days before the
+/// April 8, 2024 total solar eclipse
+const ECLIPSE_NODE_EPOCH: f64 = 2460407.5;
+
+/// How far (in days) the Sun may be from a node at new/full moon for an eclipse.
+/// The limits absorb the error of using mean lunations.
+const SOLAR_ECLIPSE_LIMIT: f64 = 15.0;
+const LUNAR_ECLIPSE_LIMIT: f64 = 16.0;
+
+/// Eclipse expected at the next new or full moon, with days until it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EclipsePrediction {
+    Lunar(i32),
+    Solar(i32),
+    None,
+}
+
+impl fmt::Display for EclipsePrediction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EclipsePrediction::Solar(0) => write!(f, "🌑 Solar Eclipse Today"),
+            EclipsePrediction::Lunar(0) => write!(f, "🌕 Lunar Eclipse Today"),
+            EclipsePrediction::Solar(days) => write!(f, "🌑 Solar eclipse in {} days", days),
+            EclipsePrediction::Lunar(days) => write!(f, "🌕 Lunar eclipse in {} days", days),
+            EclipsePrediction::None => write!(f, "No eclipse at the coming new or full moon"),
+        }
+    }
+}
+
+/// Predicts whether the next new moon or full moon (from today on) is an eclipse
+pub fn next_eclipse(jdn: i32) -> EclipsePrediction {
+    let lunar_month = ASTRONOMICAL_CYCLES["synodic_month"];
+    // The Sun crosses one of the Moon's nodes twice per eclipse year
+    let node_interval = ASTRONOMICAL_CYCLES["eclipse_year"] / 2.0;
+
+    // Mean new moon on or before today, then the syzygies that follow it
+    let lunation = ((jdn as f64 - 0.5 - NEW_MOON_EPOCH) / lunar_month).floor();
+    let new_moon = NEW_MOON_EPOCH + lunation * lunar_month;
+    let syzygies = [
+        (new_moon, true),
+        (new_moon + lunar_month / 2.0, false),
+        (new_moon + lunar_month, true),
+        (new_moon + lunar_month * 1.5, false),
+    ]
+    .into_iter()
+    .map(|(jd, solar)| ((jd + 0.5).floor() as i32, jd, solar))
+    .filter(|&(day, _, _)| day >= jdn);
+
+    // Only the next new moon and the next full moon are considered
+    for (day, jd, solar) in syzygies.take(2) {
+        let from_node = (jd - ECLIPSE_NODE_EPOCH).rem_euclid(node_interval);
+        let from_node = from_node.min(node_interval - from_node);
+        let days = day - jdn;
+        if solar && from_node < SOLAR_ECLIPSE_LIMIT {
+            return EclipsePrediction::Solar(days);
+        }
+        if !solar && from_node < LUNAR_ECLIPSE_LIMIT {
+            return EclipsePrediction::Lunar(days);
+        }
+    }
+    EclipsePrediction::None
+}
+
+/// Base of the Dresden Codex eclipse table, 9.16.4.10.8 12 Lamat 1 Muwan,
+/// in days since 0.0.0.0.0
+pub const DRESDEN_ECLIPSE_BASE: i32 = 1412848;
+/// The table runs 405 lunations in 11960 days and is then recycled
+pub const DRESDEN_ECLIPSE_DAYS: i32 = 11960;
+pub const DRESDEN_ECLIPSE_LUNATIONS: i32 = 405;
+
+/// Stations may fall this many days either side of a node before a
+/// five-lunation step is taken to pull them back
+const DRESDEN_NODE_LIMIT: f64 = 12.0;
+
+/// A station (eclipse-possible syzygy) of the Dresden Codex eclipse table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EclipseStation {
+    /// 0 for the base, then 1-68
+    pub index: usize,
+    /// Lunations since the start of the current run of the table
+    pub lunations: i32,
+    /// Days since the start of the current run of the table
+    pub day: i32,
+    /// Days since the previous station: 177 or 178 after six lunations, 148 after five
+    pub interval: i32,
+}
+
+lazy_static! {
+    static ref DRESDEN_STATIONS: Vec<EclipseStation> = dresden_stations();
+}
+
+// Lay out the table the way the codex does: steps of six lunations, with a
+// five-lunation step whenever six would carry the Sun too far past the node.
+// Six lunations take 177 or 178 days, whichever keeps the count closest to
+// the table's own lunation of 11960/405 days, and five always take 148.
+// This gives the codex's 69 intervals: 52 of 177 days, 8 of 178 and 9 of 148.
+fn dresden_stations() -> Vec<EclipseStation> {
+    let lunation = DRESDEN_ECLIPSE_DAYS as f64 / DRESDEN_ECLIPSE_LUNATIONS as f64;
+    let node_interval = ASTRONOMICAL_CYCLES["eclipse_year"] / 2.0;
+    let mut from_node = -DRESDEN_NODE_LIMIT;
+    let (mut lunations, mut day) = (0, 0);
+    let mut stations = Vec::new();
+    while lunations < DRESDEN_ECLIPSE_LUNATIONS {
+        let step = if from_node + 6.0 * lunation - node_interval <= DRESDEN_NODE_LIMIT { 6 } else { 5 };
+        from_node += step as f64 * lunation - node_interval;
+        lunations += step;
+        let interval = match step {
+            5 => 148,
+            _ if ((day + 177) as f64) < lunations as f64 * lunation - 0.5 => 178,
+            _ => 177,
+        };
+        day += interval;
+        stations.push(EclipseStation { index: stations.len() + 1, lunations, day, interval });
+    }
+    // The last interval closes the run back onto the base
+    let last = stations.pop().expect("the table has stations");
+    stations.insert(0, EclipseStation { index: 0, lunations: 0, day: 0, interval: last.interval });
+    stations
+}
+
+/// The Dresden Codex eclipse-table station falling on a day, if any. The
+/// table is anchored at its base date and repeats every 11960 days.
+pub fn dresden_eclipse_station(days_since_creation: i32) -> Option<EclipseStation> {
+    let day = (days_since_creation - DRESDEN_ECLIPSE_BASE).rem_euclid(DRESDEN_ECLIPSE_DAYS);
+    DRESDEN_STATIONS
+        .binary_search_by_key(&day, |station| station.day)
+        .ok()
+        .map(|i| DRESDEN_STATIONS[i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::correlation::jdn_to_gregorian;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_dresden_eclipse_table() {
+        assert_eq!(DRESDEN_STATIONS.len(), 69);
+        let intervals: Vec<i32> = DRESDEN_STATIONS.iter().map(|station| station.interval).collect();
+        assert_eq!(intervals.iter().sum::<i32>(), DRESDEN_ECLIPSE_DAYS);
+        assert_eq!(intervals.iter().filter(|&&i| i == 148).count(), 9);
+        assert_eq!(intervals.iter().filter(|&&i| i == 178).count(), 8);
+        assert_eq!(intervals.iter().filter(|&&i| i == 177).count(), 52);
+    }
+
+    #[test]
+    fn test_dresden_eclipse_station() {
+        // The base, 9.16.4.10.8 12 Lamat 1 Muwan
+        let base = crate::long_count::LongCount::new(9, 16, 4, 10, 8).to_days();
+        assert_eq!(base, DRESDEN_ECLIPSE_BASE);
+        let station = dresden_eclipse_station(base).unwrap();
+        assert_eq!((station.index, station.lunations, station.interval), (0, 0, 177));
+        assert_eq!(tzolkin_date(base), TzolkinDate::new(12, "Lamat"));
+
+        // The next station is six lunations later, and the table recycles after 11960 days
+        let first = dresden_eclipse_station(base + 177).unwrap();
+        assert_eq!((first.index, first.lunations), (1, 6));
+        assert_eq!(dresden_eclipse_station(base + DRESDEN_ECLIPSE_DAYS), Some(station));
+        assert_eq!(dresden_eclipse_station(base - DRESDEN_ECLIPSE_DAYS + 177), Some(first));
+        assert_eq!(dresden_eclipse_station(base + 1), None);
+        let last = dresden_eclipse_station(base - 177).unwrap();
+        assert_eq!((last.index, last.lunations, last.day), (68, 399, DRESDEN_ECLIPSE_DAYS - 177));
+    }
+
+    #[test]
+    fn test_seasonal_event_dates() {
+        // UTC dates of the equinoxes and solstices from the USNO tables
+        let expected = [
+            (2000, [(3, 20), (6, 21), (9, 22), (12, 21)]),
+            (2012, [(3, 20), (6, 20), (9, 22), (12, 21)]),
+            (2024, [(3, 20), (6, 20), (9, 22), (12, 21)]),
+            (2028, [(3, 20), (6, 20), (9, 22), (12, 21)]),
+            (2031, [(3, 20), (6, 21), (9, 23), (12, 22)]),
+        ];
+        for (year, dates) in expected {
+            for (event, (month, day)) in dates.into_iter().enumerate() {
+                assert_eq!(
+                    jdn_to_gregorian(seasonal_event_jdn(year, event)),
+                    Ok(NaiveDate::from_ymd_opt(year, month, day).unwrap()),
+                    "{} {}",
+                    year,
+                    SEASONAL_EVENTS[event].0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_next_solstice_or_equinox() {
+        // Past the winter solstice the next event is the following spring
+        assert_eq!(next_solstice_or_equinox(2024, 12, 25), Ok(("Spring Equinox".to_string(), 85)));
+        assert_eq!(next_solstice_or_equinox(2024, 11, 1), Ok(("Winter Solstice".to_string(), 50)));
+        assert_eq!(next_solstice_or_equinox(2024, 6, 25), Ok(("Autumn Equinox".to_string(), 89)));
+        assert_eq!(next_solstice_or_equinox(2024, 1, 1), Ok(("Spring Equinox".to_string(), 79)));
+        assert_eq!(
+            next_solstice_or_equinox(0, 13, 1),
+            Err(DateError::Gregorian { year: 0, month: 13, day: 1 })
+        );
+        assert!(next_solstice_or_equinox(2023, 2, 29).is_err());
+    }
+
+    #[test]
+    fn test_next_eclipse() {
+        // Total solar eclipse of April 8, 2024 and the eclipses around it
+        assert_eq!(next_eclipse(gregorian_to_jdn(2024, 4, 8)), EclipsePrediction::Solar(0));
+        assert_eq!(next_eclipse(gregorian_to_jdn(2024, 4, 1)), EclipsePrediction::Solar(7));
+        assert_eq!(next_eclipse(gregorian_to_jdn(2024, 3, 20)), EclipsePrediction::Lunar(5));
+        assert_eq!(next_eclipse(gregorian_to_jdn(2024, 9, 18)), EclipsePrediction::Lunar(0));
+        assert_eq!(next_eclipse(gregorian_to_jdn(2025, 3, 15)), EclipsePrediction::Solar(14));
+
+        // Ordinary new and full moons away from the nodes
+        assert_eq!(next_eclipse(gregorian_to_jdn(2024, 1, 1)), EclipsePrediction::None);
+        assert_eq!(next_eclipse(gregorian_to_jdn(2024, 4, 10)), EclipsePrediction::None);
+        assert_eq!(next_eclipse(gregorian_to_jdn(2024, 6, 15)), EclipsePrediction::None);
+    }
+
+    #[test]
+    fn test_lunar_series() {
+        let gmt = 584283;
+        // Day of the reference new moon, then the 30-day and 29-day months after it
+        let epoch = gregorian_to_jdn(2000, 1, 6) - gmt;
+        assert_eq!(lunar_series(epoch), LunarSeries { age: 0, lunation: 1, month_length: 30 });
+        assert_eq!(lunar_series(epoch - 1).lunation, 6);
+        assert_eq!(lunar_series(epoch + 29), LunarSeries { age: 29, lunation: 1, month_length: 30 });
+        assert_eq!(lunar_series(epoch + 30), LunarSeries { age: 0, lunation: 2, month_length: 29 });
+
+        // 13.0.0.0.0 fell eight days after the new moon of 13 December 2012
+        assert_eq!(lunar_series(1_872_000).age, 8);
+
+        // Ages always fit their month, and months average a synodic month
+        let start = 1_000_000;
+        let mut new_moons = 0;
+        for days in start..start + 29_531 {
+            let series = lunar_series(days);
+            assert!(series.month_length == 29 || series.month_length == 30);
+            assert!(series.age < series.month_length);
+            assert!((1..=6).contains(&series.lunation));
+            if series.age == 0 {
+                new_moons += 1;
+            }
+        }
+        assert!((1000..=1001).contains(&new_moons));
+    }
+
+    #[test]
+    fn test_year_bearer_sequence() {
+        // The Haab' year starting 2 April 2013, after 13.0.0.0.0, and those following;
+        // the number advances by one each year since 365 = 28 * 13 + 1
+        let year_start = 1_872_102;
+        let expected = ["2 Ik'", "3 Manik'", "4 Eb'", "5 Kab'an", "6 Ik'"];
+        for (year, bearer) in expected.iter().enumerate() {
+            let start = year_start + 365 * year as i32;
+            assert_eq!(year_bearer(start), *bearer);
+            assert_eq!(year_bearer(start + 364), *bearer);
+        }
+        assert_eq!(year_bearer(year_start - 1), "1 Kab'an");
+
+        // Landa's year of 16 July 1553 (Julian) was borne by 12 K'an
+        let landa = 2_288_488 - 584_283;
+        assert_eq!(year_bearer_date(landa, YearBearerSystem::Yucatec), TzolkinDate::new(12, "K'an"));
+        assert_eq!(year_bearer_date(landa, YearBearerSystem::Puuc), TzolkinDate::new(11, "Ak'b'al"));
+        assert_eq!(year_bearer_date(landa, YearBearerSystem::Classic), TzolkinDate::new(10, "Ik'"));
+    }
+
+    #[test]
+    fn test_moon_phase_known_dates() {
+        // New moons
+        assert_eq!(moon_phase(gregorian_to_jdn(2000, 1, 6)), MoonPhase::New);
+        assert_eq!(moon_phase(gregorian_to_jdn(2012, 12, 13)), MoonPhase::New);
+        assert_eq!(moon_phase(gregorian_to_jdn(2024, 4, 8)), MoonPhase::New);
+        assert_eq!(moon_phase(gregorian_to_jdn(1991, 7, 11)), MoonPhase::New);
+
+        // Full moons
+        assert_eq!(moon_phase(gregorian_to_jdn(2000, 1, 21)), MoonPhase::Full);
+        assert_eq!(moon_phase(gregorian_to_jdn(2012, 12, 28)), MoonPhase::Full);
+        assert_eq!(moon_phase(gregorian_to_jdn(2024, 1, 25)), MoonPhase::Full);
+        assert_eq!(moon_phase(gregorian_to_jdn(1990, 2, 9)), MoonPhase::Full);
+    }
+
+    #[test]
+    fn test_venus_phase_heliacal_rise() {
+        // Venus reappeared as morning star in mid-January 2022 and mid-August 2023
+        let (phase, day) = venus_phase(gregorian_to_jdn(2022, 1, 16));
+        assert_eq!(phase, VenusPhase::MorningStar);
+        assert!(day < 10, "expected early in the cycle, got day {}", day);
+
+        let (phase, day) = venus_phase(gregorian_to_jdn(2023, 8, 22));
+        assert_eq!(phase, VenusPhase::MorningStar);
+        assert!(day < 10, "expected early in the cycle, got day {}", day);
+
+        // Superior conjunction of Venus: June 4, 2024
+        let (phase, _) = venus_phase(gregorian_to_jdn(2024, 6, 4));
+        assert_eq!(phase, VenusPhase::SuperiorConjunction);
+
+        // Inferior conjunction of Venus: March 23, 2025
+        let (phase, _) = venus_phase(gregorian_to_jdn(2025, 3, 23));
+        assert_eq!(phase, VenusPhase::InferiorConjunction);
+        assert_eq!(phase.to_string(), "⭐ Inferior Conjunction");
+    }
+
+    #[test]
+    fn test_phase_and_eclipse_display() {
+        // The variants the standalone v0.0.5 calculator matches on, as this
+        // module produces them. It also called the last days of the month New,
+        // and here the days just before the new moon are New too.
+        let new_moon = gregorian_to_jdn(2000, 1, 6);
+        assert_eq!(moon_phase(new_moon + 29), MoonPhase::New);
+        assert_eq!(moon_phase(new_moon + 29).to_string(), "🌑 New Moon");
+        assert_eq!(moon_phase(new_moon + 8), MoonPhase::FirstQuarter);
+        assert_eq!(moon_phase(new_moon + 8).to_string(), "🌓 First Quarter");
+        assert_eq!(moon_phase(new_moon + 15).to_string(), "🌕 Full Moon");
+        assert_eq!(moon_phase(new_moon + 22), MoonPhase::LastQuarter);
+        assert_eq!(moon_phase(new_moon + 22).to_string(), "🌗 Last Quarter");
+
+        // Venus rose as morning star on 16 January 2022 and was evening star a year later
+        let (phase, _) = venus_phase(gregorian_to_jdn(2022, 1, 20));
+        assert_eq!(phase.to_string(), "🌅 Morning Star");
+        let (phase, _) = venus_phase(gregorian_to_jdn(2023, 2, 20));
+        assert_eq!(phase, VenusPhase::EveningStar);
+        assert_eq!(phase.to_string(), "🌇 Evening Star");
+
+        // Eclipse payloads count the days to the syzygy
+        let lunar = next_eclipse(gregorian_to_jdn(2024, 9, 18));
+        assert_eq!(lunar.to_string(), "🌕 Lunar Eclipse Today");
+        let solar = next_eclipse(gregorian_to_jdn(2024, 4, 1));
+        assert_eq!(solar.to_string(), "🌑 Solar eclipse in 7 days");
+    }
+}
@@ -0,0 +1,188 @@
+use std::borrow::Cow;
+use std::io::{self, BufRead, Write};
+
+use chrono::Datelike;
+
+use crate::astronomical::year_bearer;
+use crate::date_utils::{calendar_round_string, gregorian_to_jdn, haab_date, tzolkin_date, CalendarSystem};
+use crate::long_count::LongCount;
+
+/// Header row written by `convert_csv`
+pub const OUTPUT_HEADER: &str = "input,gregorian,long_count,calendar_round,year_bearer,error";
+
+/// Row counts from a batch conversion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BatchSummary {
+    pub rows: usize,
+    pub errors: usize,
+}
+
+// Days since 0.0.0.0.0 for an ISO date, written in `calendar`, or a dotted Long Count
+fn parse_cell(cell: &str, correlation: i32, calendar: CalendarSystem) -> Result<i32, String> {
+    if cell.contains('.') {
+        let lc = cell
+            .parse::<LongCount>()
+            .map_err(|e| format!("Invalid Long Count '{}': {}", cell, e))?;
+        Ok(lc.to_days())
+    } else {
+        let date = calendar
+            .parse_date(cell)
+            .map_err(|e| format!("Invalid date '{}': {}", cell, e))?;
+        Ok(gregorian_to_jdn(date.year(), date.month() as i32, date.day() as i32) - correlation)
+    }
+}
+
+// The first field of a CSV line, unquoting it if needed
+fn first_field(line: &str) -> String {
+    let Some(rest) = line.strip_prefix('"') else {
+        return line.split(',').next().unwrap_or("").trim().to_string();
+    };
+    let mut field = String::new();
+    let mut chars = rest.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            // A doubled quote is a literal quote; a lone one ends the field
+            '"' if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => break,
+            c => field.push(c),
+        }
+    }
+    field.trim().to_string()
+}
+
+fn escape(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+// The gregorian, long_count, calendar_round and year_bearer columns for one input cell
+fn convert_cell(cell: &str, correlation: i32, calendar: CalendarSystem) -> Result<[String; 4], String> {
+    let days = parse_cell(cell, correlation, calendar)?;
+    let long_count = LongCount::from_days(days);
+    // Seven- and eight-place counts can parse yet fall past chrono's calendar
+    let gregorian = long_count
+        .to_gregorian(correlation)
+        .map_err(|e| format!("No Gregorian date for '{}': {}", cell, e))?;
+    Ok([
+        gregorian.to_string(),
+        long_count.to_string(),
+        calendar_round_string(&tzolkin_date(days), &haab_date(days)),
+        year_bearer(days),
+    ])
+}
+
+/// Reads the first column of `input` as ISO dates or Long Counts and writes
+/// one converted row per line to `output`. Dates are read in `calendar`; the
+/// gregorian column is always proleptic Gregorian. A leading header row is
+/// skipped, and rows that don't parse get a message in the error column
+/// instead of stopping the run.
+pub fn convert_csv<R: BufRead, W: Write>(
+    input: R,
+    mut output: W,
+    correlation: i32,
+    calendar: CalendarSystem,
+) -> io::Result<BatchSummary> {
+    let mut summary = BatchSummary::default();
+    writeln!(output, "{}", OUTPUT_HEADER)?;
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        let cell = first_field(line.trim_end_matches('\r'));
+        if cell.is_empty() {
+            continue;
+        }
+        let converted = convert_cell(&cell, correlation, calendar);
+        // A first row with no digits in it is a column heading
+        if index == 0 && converted.is_err() && !cell.contains(|c: char| c.is_ascii_digit()) {
+            continue;
+        }
+        summary.rows += 1;
+        match converted {
+            Ok(columns) => {
+                let columns: Vec<_> = columns.iter().map(|c| escape(c)).collect();
+                writeln!(output, "{},{},", escape(&cell), columns.join(","))?;
+            }
+            Err(e) => {
+                summary.errors += 1;
+                writeln!(output, "{},,,,,{}", escape(&cell), escape(&e))?;
+            }
+        }
+    }
+    output.flush()?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(input: &str) -> (String, BatchSummary) {
+        let mut output = Vec::new();
+        let summary = convert_csv(input.as_bytes(), &mut output, 584283, CalendarSystem::Gregorian).unwrap();
+        (String::from_utf8(output).unwrap(), summary)
+    }
+
+    #[test]
+    fn test_convert_csv() {
+        let (output, summary) = convert("date,site\n2012-12-21,Tortuguero\n\"9.12.11.5.18\",Palenque\n");
+        assert_eq!(summary, BatchSummary { rows: 2, errors: 0 });
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], OUTPUT_HEADER);
+        assert_eq!(lines[1], "2012-12-21,2012-12-21,13.0.0.0.0,4 Ajaw 3 K'ank'in,1 Kab'an,");
+        assert_eq!(lines[2], "9.12.11.5.18,0683-08-29,9.12.11.5.18,6 Etz'nab' 11 Yax,10 Manik',");
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_convert_csv_keeps_going_after_bad_rows() {
+        let (output, summary) = convert("2012-13-01\n9.18\n\n2012-12-21\n");
+        assert_eq!(summary, BatchSummary { rows: 3, errors: 2 });
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[1].starts_with("2012-13-01,,,,,"));
+        assert!(lines[2].starts_with("9.18,,,,,"));
+        assert!(lines[3].starts_with("2012-12-21,2012-12-21,13.0.0.0.0,"));
+    }
+
+    #[test]
+    fn test_convert_csv_out_of_range_long_counts() {
+        let (output, summary) =
+            convert("1.0.0.0.0.0.0.0\n2.0.0.0.0.0.0\n19.19.19.19.19.19.17.19\n1.0.0.0.0.0.0\n9.12.11.5.18\n");
+        assert_eq!(summary, BatchSummary { rows: 5, errors: 3 });
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[1],
+            "1.0.0.0.0.0.0.0,,,,,No Gregorian date for '1.0.0.0.0.0.0.0': \
+             Julian Day Number 1152584283 is outside the supported Gregorian range"
+        );
+        assert!(lines[2].starts_with("2.0.0.0.0.0.0,,,,,No Gregorian date"));
+        assert!(lines[3].starts_with("19.19.19.19.19.19.17.19,,,,,Invalid Long Count"));
+        assert!(lines[4].starts_with("1.0.0.0.0.0.0,+154591-01-20,1.0.0.0.0.0.0,"));
+        assert!(lines[5].starts_with("9.12.11.5.18,0683-08-29,"));
+    }
+
+    #[test]
+    fn test_convert_csv_julian_dates() {
+        let mut output = Vec::new();
+        let input = "1511-08-01\n1697-03-13\n1582-10-10\n";
+        let summary = convert_csv(input.as_bytes(), &mut output, 584283, CalendarSystem::Historical).unwrap();
+        assert_eq!(summary, BatchSummary { rows: 3, errors: 1 });
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[1].starts_with("1511-08-01,1511-08-11,11.14.11.6.0,"), "{}", lines[1]);
+        assert!(lines[2].starts_with("1697-03-13,1697-03-13,"), "{}", lines[2]);
+        assert_eq!(lines[3], "1582-10-10,,,,,Invalid date '1582-10-10': Invalid Julian date 1582-10-10");
+    }
+
+    #[test]
+    fn test_first_field_and_escape() {
+        assert_eq!(first_field(" 2012-12-21 ,x"), "2012-12-21");
+        assert_eq!(first_field("\"a \"\"b\"\", c\",d"), "a \"b\", c");
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape("a, \"b\""), "\"a, \"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,453 @@
+use std::sync::{Arc, RwLock};
+use std::num::NonZeroUsize;
+use lru::LruCache;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Datelike, TimeZone};
+
+use crate::astronomical::{
+    moon_phase,
+    venus_phase,
+    year_bearer,
+    next_seasonal_event,
+    next_eclipse,
+    lunar_series,
+    EclipsePrediction,
+    LunarSeries,
+    MoonPhase,
+    VenusPhase,
+};
+use crate::correlation::days_to_gregorian;
+use crate::date_utils::{
+    calendar_round_string, gregorian_to_jdn, count_819, lord_of_the_night, CalendarRound, DateError, TzolkinDate, HaabDate,
+};
+pub use crate::date_utils::Station819;
+use crate::history::historical_event;
+use crate::long_count::LongCount;
+use crate::mayan_date::MayanDate;
+use crate::metrics::{Instant, Metrics};
+
+// Calendar Cache
+// Keyed on (days since creation, correlation) since the same day count lands
+// on a different date under each correlation
+pub struct CalendarCache {
+    cache: LruCache<(i32, i32), CalendarData>,
+}
+impl CalendarCache {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            cache: LruCache::new(capacity),
+        }
+    }
+    // LruCache::get requires mutable access, so we use &mut self.
+    pub fn get_calendar_data(&mut self, days: i32, correlation: i32) -> Option<CalendarData> {
+        self.cache.get(&(days, correlation)).cloned()
+    }
+    pub fn put_calendar_data(&mut self, days: i32, correlation: i32, data: CalendarData) {
+        self.cache.put((days, correlation), data);
+    }
+
+    /// Writes every entry to `path` as JSON, least recently used first so
+    /// `load` restores the same order
+    #[cfg(feature = "serde")]
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let saved = SavedCache {
+            capacity: self.cache.cap().get(),
+            entries: self.cache.iter().rev().map(|(&(days, correlation), data)| (days, correlation, data.clone())).collect(),
+        };
+        let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        serde_json::to_writer(file, &saved)?;
+        Ok(())
+    }
+
+    /// Reads a cache written by `save`
+    #[cfg(feature = "serde")]
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        let saved: SavedCache = serde_json::from_reader(file)?;
+        let capacity = NonZeroUsize::new(saved.capacity)
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "cache capacity is zero"))?;
+        let mut cache = Self::new(capacity);
+        for (days, correlation, data) in saved.entries {
+            cache.put_calendar_data(days, correlation, data);
+        }
+        Ok(cache)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+// On-disk form of a CalendarCache
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedCache {
+    capacity: usize,
+    entries: Vec<(i32, i32, CalendarData)>,
+}
+
+/// The next solstice or equinox and how many days away it is
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeasonalEvent {
+    pub name: String,
+    pub days_until: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CalendarData {
+    /// The calendar positions themselves; everything else is for display
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub mayan: MayanDate,
+    pub moon_phase: MoonPhase,
+    pub lunar_series: LunarSeries,
+    pub venus_phase: VenusPhase,
+    pub venus_cycle_day: i32,
+    pub year_bearer: String,
+    pub next_solstice: SeasonalEvent,
+    pub eclipse_status: EclipsePrediction,
+    pub historical_event: Option<String>,
+    pub gregorian_date: NaiveDate,
+    pub julian_day_number: i32,
+    pub days_since_creation: i32,
+}
+
+impl CalendarData {
+    pub fn new(date: NaiveDateTime, correlation: i32) -> Self {
+        let gregorian = date.date();
+        let julian_day_number = gregorian_to_jdn(
+            gregorian.year(),
+            gregorian.month() as i32,
+            gregorian.day() as i32,
+        );
+        Self::from_jdn(julian_day_number, gregorian, correlation)
+    }
+
+    /// Calendar data for the civil date of `datetime` in its own time zone
+    pub fn from_datetime<Tz: TimeZone>(datetime: &DateTime<Tz>, correlation: i32) -> Self {
+        Self::new(datetime.naive_local(), correlation)
+    }
+
+    /// Calendar data for a day counted from 0.0.0.0.0, or an error if the day
+    /// has no Gregorian date chrono can represent
+    pub fn from_days(days: i32, correlation: i32) -> Result<Self, DateError> {
+        let gregorian = days_to_gregorian(days, correlation)?;
+        Ok(Self::from_jdn(days + correlation, gregorian, correlation))
+    }
+
+    /// Calendar data for the day a Long Count names
+    pub fn from_long_count(long_count: LongCount, correlation: i32) -> Result<Self, DateError> {
+        Self::from_days(long_count.to_days(), correlation)
+    }
+
+    fn from_jdn(julian_day_number: i32, gregorian: NaiveDate, correlation: i32) -> Self {
+        // The correlation is the JDN of 0.0.0.0.0 (584283 under GMT)
+        let days_since_creation = julian_day_number - correlation;
+        let (venus_phase, venus_cycle_day) = venus_phase(julian_day_number);
+        let (solstice, days_to_event) = next_seasonal_event(gregorian);
+        Self {
+            mayan: MayanDate::from_days(days_since_creation),
+            moon_phase: moon_phase(julian_day_number),
+            lunar_series: lunar_series(days_since_creation),
+            venus_phase,
+            venus_cycle_day,
+            year_bearer: year_bearer(days_since_creation),
+            next_solstice: SeasonalEvent { name: solstice, days_until: days_to_event },
+            eclipse_status: next_eclipse(julian_day_number),
+            historical_event: historical_event(julian_day_number).map(|s| s.to_string()),
+            gregorian_date: gregorian,
+            julian_day_number,
+            days_since_creation,
+        }
+    }
+
+    /// Tzolk'in and Haab' together, e.g. "4 Ajaw 8 Kumk'u"
+    pub fn calendar_round(&self) -> String {
+        calendar_round_string(&self.mayan.tzolkin, &self.mayan.haab)
+    }
+
+    pub fn new_from_components(
+        long_count: LongCount,
+        tzolkin: TzolkinDate,
+        haab: HaabDate,
+        days: i32,
+    ) -> Self {
+        let calendar_round = CalendarRound { tzolkin: tzolkin.clone(), haab: haab.clone() };
+        Self {
+            mayan: MayanDate {
+                long_count,
+                tzolkin,
+                haab,
+                lord_of_night: lord_of_the_night(days),
+                count_819: count_819(days).into(),
+                calendar_round,
+            },
+            moon_phase: MoonPhase::New,
+            lunar_series: lunar_series(days),
+            venus_phase: VenusPhase::MorningStar,
+            venus_cycle_day: 0,
+            year_bearer: String::new(),
+            next_solstice: SeasonalEvent::default(),
+            eclipse_status: EclipsePrediction::None,
+            historical_event: None,
+            gregorian_date: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            julian_day_number: 0,
+            days_since_creation: days,
+        }
+    }
+}
+
+pub struct ParallelCalendarCalculator {
+    metrics: Arc<Metrics>,
+    cache: Arc<RwLock<CalendarCache>>,
+    correlation: i32,
+}
+
+impl ParallelCalendarCalculator {
+    pub fn new(cache: Arc<RwLock<CalendarCache>>, metrics: Arc<Metrics>, correlation: i32) -> Self {
+        Self { metrics, cache, correlation }
+    }
+    
+    #[tracing::instrument(
+        name = "calculate_calendar_data",
+        level = "debug",
+        skip(self),
+        fields(correlation = self.correlation, cache_hit = tracing::field::Empty)
+    )]
+    pub fn calculate_new_data(&self, days: i32) -> Result<CalendarData, DateError> {
+        let span = tracing::Span::current();
+        if let Some(data) = self.cache.write().unwrap().get_calendar_data(days, self.correlation) {
+            span.record("cache_hit", true);
+            self.metrics.record_cache_hit();
+            return Ok(data);
+        }
+        span.record("cache_hit", false);
+        self.metrics.record_cache_miss();
+        let start = Instant::now();
+        let data = CalendarData::from_days(days, self.correlation)?;
+        self.metrics.record_calculation(start.elapsed());
+        self.cache.write().unwrap().put_calendar_data(days, self.correlation, data.clone());
+        Ok(data)
+    }
+
+    /// Calendar data for every day in `days`, computed across the rayon pool.
+    /// It bypasses the cache, whose lock would serialize the workers.
+    #[cfg(feature = "parallel")]
+    pub fn calculate_date_range(&self, days: std::ops::Range<i32>) -> Result<Vec<CalendarData>, DateError> {
+        use rayon::prelude::*;
+        let start = Instant::now();
+        let data = days
+            .into_par_iter()
+            .map(|day| CalendarData::from_days(day, self.correlation))
+            .collect();
+        self.metrics.record_calculation(start.elapsed());
+        data
+    }
+
+    /// Lazily yields one day after another starting at `start_days`, going
+    /// through the cache like `calculate_new_data`. It stops at the last day
+    /// with a Gregorian date.
+    pub fn iter_dates(&self, start_days: i32) -> impl Iterator<Item = CalendarData> + '_ {
+        (start_days..).map_while(move |days| self.calculate_new_data(days).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::correlation::Correlation;
+    use crate::date_utils::{haab_date, tzolkin_date};
+
+    #[test]
+    fn test_iter_dates_finds_calendar_round() {
+        let cache = Arc::new(RwLock::new(CalendarCache::new(NonZeroUsize::new(16).unwrap())));
+        let calculator = ParallelCalendarCalculator::new(
+            Arc::clone(&cache),
+            Arc::new(Metrics::new()),
+            Correlation::GMT.jdn_offset(),
+        );
+        let mut days = calculator.iter_dates(0);
+        let creation = days.next().unwrap();
+        assert_eq!(creation.mayan.long_count, LongCount::from_days(0));
+        assert_eq!(creation.gregorian_date, NaiveDate::from_ymd_opt(-3113, 8, 11).unwrap());
+
+        // 4 Ajaw 8 Kumk'u comes round again after 18980 days
+        let next = days
+            .find(|d| d.mayan.tzolkin == creation.mayan.tzolkin && d.mayan.haab == creation.mayan.haab)
+            .unwrap();
+        assert_eq!(next.days_since_creation, 18980);
+        assert_eq!(next.calendar_round(), "4 Ajaw 8 Kumk'u");
+        assert!(cache.write().unwrap().get_calendar_data(18980, Correlation::GMT.jdn_offset()).is_some());
+    }
+
+    #[test]
+    fn test_cache_keeps_correlations_apart() {
+        let cache = Arc::new(RwLock::new(CalendarCache::new(NonZeroUsize::new(16).unwrap())));
+        let metrics = Arc::new(Metrics::new());
+        let gmt = ParallelCalendarCalculator::new(Arc::clone(&cache), Arc::clone(&metrics), 584283);
+        let other = ParallelCalendarCalculator::new(Arc::clone(&cache), Arc::clone(&metrics), 584285);
+
+        let days = 1_872_000;
+        assert_eq!(gmt.calculate_new_data(days).unwrap().gregorian_date, NaiveDate::from_ymd_opt(2012, 12, 21).unwrap());
+        // A second correlation sharing the cache must not be handed the GMT date
+        assert_eq!(other.calculate_new_data(days).unwrap().gregorian_date, NaiveDate::from_ymd_opt(2012, 12, 23).unwrap());
+        assert_eq!(gmt.calculate_new_data(days).unwrap().gregorian_date, NaiveDate::from_ymd_opt(2012, 12, 21).unwrap());
+    }
+
+    #[test]
+    fn test_calculate_new_data_span_records_cache_hit() {
+        use std::sync::Mutex;
+        use tracing_subscriber::fmt::format::FmtSpan;
+
+        // Collects the formatted log lines so the span fields can be checked
+        #[derive(Clone, Default)]
+        struct Captured(Arc<Mutex<Vec<u8>>>);
+        impl std::io::Write for Captured {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let cache = Arc::new(RwLock::new(CalendarCache::new(NonZeroUsize::new(16).unwrap())));
+            let calculator = ParallelCalendarCalculator::new(cache, Arc::new(Metrics::new()), 584283);
+            calculator.calculate_new_data(1_872_000).unwrap();
+            calculator.calculate_new_data(1_872_000).unwrap();
+        });
+        let log = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        let closed: Vec<&str> = log.lines().filter(|line| line.contains("calculate_calendar_data")).collect();
+        assert_eq!(closed.len(), 2, "{}", log);
+        assert!(closed[0].contains("days=1872000") && closed[0].contains("cache_hit=false"), "{}", closed[0]);
+        assert!(closed[1].contains("correlation=584283") && closed[1].contains("cache_hit=true"), "{}", closed[1]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_calculate_date_range_matches_serial() {
+        let cache = Arc::new(RwLock::new(CalendarCache::new(NonZeroUsize::new(16).unwrap())));
+        let calculator = ParallelCalendarCalculator::new(cache, Arc::new(Metrics::new()), 584283);
+        let range = calculator.calculate_date_range(1_872_000..1_872_100).unwrap();
+        let serial: Vec<CalendarData> = (1_872_000..1_872_100).map(|d| CalendarData::from_days(d, 584283).unwrap()).collect();
+        assert_eq!(range, serial);
+    }
+
+    // Dates from published inscriptions, as proleptic Gregorian under GMT
+    const KNOWN_DATES: [((i32, u32, u32), &str, &str); 7] = [
+        ((-3113, 8, 11), "0.0.0.0.0", "4 Ajaw 8 Kumk'u"),     // Creation, Quiriguá Stela C
+        ((-35, 12, 6), "7.16.3.2.13", "6 B'en 16 Xul"),       // Chiapa de Corzo Stela 2
+        ((378, 1, 14), "8.17.1.4.12", "11 Eb' 15 Mak"),       // Tikal Stela 31, arrival of Siyaj K'ak'
+        ((603, 3, 24), "9.8.9.13.0", "8 Ajaw 13 Pop"),        // Birth of Pakal, Palenque
+        ((682, 5, 4), "9.12.9.17.16", "5 Kib' 14 Sotz'"),     // Jasaw Chan K'awiil I accession, Tikal Temple I
+        ((692, 1, 8), "9.12.19.14.12", "5 Eb' 5 K'ayab"),     // Cross Group dedication, Palenque
+        ((2012, 12, 21), "13.0.0.0.0", "4 Ajaw 3 K'ank'in"),  // Bak'tun ending, Tortuguero Monument 6
+    ];
+
+    #[test]
+    fn test_known_inscription_dates() {
+        let correlation = Correlation::GMT.jdn_offset();
+        for ((year, month, day), long_count, calendar_round) in KNOWN_DATES {
+            let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+            let data = CalendarData::new(date.and_hms_opt(12, 0, 0).unwrap(), correlation);
+            assert_eq!(data.mayan.long_count.to_string(), long_count, "{}", date);
+            assert_eq!(data.calendar_round(), calendar_round, "{}", long_count);
+
+            // Each calculator on its own agrees with the combined data
+            let parsed: LongCount = long_count.parse().unwrap();
+            let days = parsed.to_days();
+            assert_eq!(gregorian_to_jdn(year, month as i32, day as i32) - correlation, days);
+            assert_eq!(parsed.to_gregorian(correlation), Ok(date));
+            assert_eq!(calendar_round_string(&tzolkin_date(days), &haab_date(days)), calendar_round);
+            assert_eq!(CalendarData::from_days(days, correlation), Ok(data.clone()));
+            assert_eq!(CalendarData::from_long_count(parsed, correlation), Ok(data));
+        }
+    }
+
+    #[test]
+    fn test_calendar_data_out_of_range() {
+        let correlation = Correlation::GMT.jdn_offset();
+        let last: LongCount = "1.0.0.0.0.0.0".parse().unwrap();
+        assert!(CalendarData::from_long_count(last, correlation).is_ok());
+        for s in ["2.0.0.0.0.0.0", "1.0.0.0.0.0.0.0"] {
+            let lc: LongCount = s.parse().unwrap();
+            assert!(CalendarData::from_long_count(lc, correlation).is_err(), "{}", s);
+        }
+        assert!(CalendarData::from_days(i32::MAX, correlation).is_err());
+        assert!(CalendarData::from_days(i32::MIN, correlation).is_err());
+
+        // Iteration ends at the last representable day rather than panicking
+        let cache = Arc::new(RwLock::new(CalendarCache::new(NonZeroUsize::new(16).unwrap())));
+        let calculator = ParallelCalendarCalculator::new(cache, Arc::new(Metrics::new()), correlation);
+        let end = NaiveDate::MAX;
+        let end_days = gregorian_to_jdn(end.year(), end.month() as i32, end.day() as i32) - correlation;
+        let tail: Vec<CalendarData> = calculator.iter_dates(end_days - 2).collect();
+        assert_eq!(tail.len(), 3);
+        assert_eq!(tail[2].gregorian_date, end);
+        assert!(calculator.calculate_new_data(end_days + 1).is_err());
+    }
+
+    #[test]
+    fn test_calendar_data_from_datetime() {
+        use chrono::{FixedOffset, Utc};
+        let correlation = Correlation::GMT.jdn_offset();
+        // 02:00 UTC on the 21st is 09:00 in Bangkok and 20:00 the day before in Mexico City
+        let utc = Utc.with_ymd_and_hms(2012, 12, 21, 2, 0, 0).unwrap();
+        let bangkok = utc.with_timezone(&FixedOffset::east_opt(7 * 3600).unwrap());
+        let mexico_city = utc.with_timezone(&FixedOffset::west_opt(6 * 3600).unwrap());
+        let day_of = |day| {
+            let date = NaiveDate::from_ymd_opt(2012, 12, day).unwrap();
+            CalendarData::new(date.and_hms_opt(0, 0, 0).unwrap(), correlation)
+        };
+        assert_eq!(CalendarData::from_datetime(&utc, correlation).mayan, day_of(21).mayan);
+        assert_eq!(CalendarData::from_datetime(&bangkok, correlation).mayan, day_of(21).mayan);
+        assert_eq!(CalendarData::from_datetime(&mexico_city, correlation).mayan, day_of(20).mayan);
+        assert_eq!(CalendarData::from_datetime(&mexico_city, correlation).gregorian_date.to_string(), "2012-12-20");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_calendar_data_serde_round_trip() {
+        let now = chrono::Local::now().naive_local();
+        let data = CalendarData::new(now, Correlation::GMT.jdn_offset());
+        let json = serde_json::to_string(&data).unwrap();
+        let parsed: CalendarData = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, data);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_calendar_cache_save_load() {
+        let mut cache = CalendarCache::new(NonZeroUsize::new(3).unwrap());
+        for days in 1..=3 {
+            cache.put_calendar_data(days, 584283, CalendarData::from_days(days, 584283).unwrap());
+        }
+        // Touch day 1 so day 2 is the least recently used
+        assert!(cache.get_calendar_data(1, 584283).is_some());
+        let path = std::env::temp_dir().join(format!("mayan_calendar_cache_{}.json", std::process::id()));
+        cache.save(&path).unwrap();
+        let mut loaded = CalendarCache::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 3);
+        loaded.put_calendar_data(4, 584283, CalendarData::from_days(4, 584283).unwrap());
+        assert_eq!(loaded.get_calendar_data(2, 584283), None);
+        for days in [1, 3, 4] {
+            assert_eq!(loaded.get_calendar_data(days, 584283), CalendarData::from_days(days, 584283).ok());
+        }
+        assert!(CalendarCache::load(&path).is_err());
+    }
+}
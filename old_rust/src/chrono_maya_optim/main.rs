@@ -1,538 +0,0 @@
-use std::sync::Arc;
-use std::sync::RwLock;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::collections::HashMap;
-use std::num::NonZeroUsize;
-use lru::LruCache;
-use chrono::{NaiveDate, NaiveDateTime, Datelike};
-
-
-use eframe::{App, NativeOptions};
-use egui::{self, Context, TextureHandle, ColorImage, TextureOptions, Vec2, ViewportBuilder};
-use tracing::{error, Level};
-use tracing_subscriber::EnvFilter;
-
-// Local module imports
-mod config;
-mod date_utils;
-mod astronomical;
-use config::Config;
-use date_utils::{tzolkin_date, haab_date, TzolkinDate, HaabDate};
-use astronomical::{
-    moon_phase,
-    venus_phase,
-    year_bearer,
-    next_solstice_or_equinox,
-    next_eclipse,
-    historical_event,
-};
-
-// Enum for Glyph Types
-#[derive(Debug, Clone, Copy)]
-pub enum GlyphType {
-    Tzolkin,
-    Haab,
-}
-
-//  MetriPerformancecs
-#[derive(Default)]
-pub struct Metrics {
-    calculation_time: AtomicU64,
-    glyph_load_time: AtomicU64,
-    render_time: AtomicU64,
-    cache_hits: AtomicU64,
-    cache_misses: AtomicU64,
-}
-
-impl Metrics {
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    pub fn record_calculation(&self, duration: std::time::Duration) {
-        self.calculation_time.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
-    }
-
-    pub fn record_cache_hit(&self) {
-        self.cache_hits.fetch_add(1, Ordering::Relaxed);
-    }
-
-    pub fn record_cache_miss(&self) {
-        self.cache_misses.fetch_add(1, Ordering::Relaxed);
-    }
-
-    pub fn report(&self) -> String {
-        format!(
-            "Performance Metrics:\n\
-             Calculation Time: {}µs\n\
-             Cache Hits: {}\n\
-             Cache Misses: {}\n\
-             Cache Hit Rate: {:.2}%",
-            self.calculation_time.load(Ordering::Relaxed),
-            self.cache_hits.load(Ordering::Relaxed),
-            self.cache_misses.load(Ordering::Relaxed),
-            self.cache_hit_rate() * 100.0
-        )
-    }
-
-    fn cache_hit_rate(&self) -> f64 {
-        let hits = self.cache_hits.load(Ordering::Relaxed) as f64;
-        let misses = self.cache_misses.load(Ordering::Relaxed) as f64;
-        let total = hits + misses;
-        if total > 0.0 {
-            hits / total
-        } else {
-            0.0
-        }
-    }
-}
-
-// Texture Cache
-pub struct TextureCache {
-    tzolkin_textures: HashMap<String, TextureHandle>,
-    haab_textures: HashMap<String, TextureHandle>,
-}
-
-// Calendar Cache
-pub struct CalendarCache {
-    cache: LruCache<i32, CalendarData>,
-}
-impl CalendarCache {
-    pub fn new(capacity: NonZeroUsize) -> Self {
-        Self {
-            cache: LruCache::new(capacity),
-        }
-    }
-    // LruCache::get requires mutable access, so we use &mut self.
-    pub fn get_calendar_data(&mut self, days: i32) -> Option<CalendarData> {
-        self.cache.get(&days).cloned()
-    }
-    pub fn put_calendar_data(&mut self, days: i32, data: CalendarData) {
-        self.cache.put(days, data);
-    }
-}
-
-// Glyph Error Handling
-#[derive(Debug, thiserror::Error)]
-pub enum GlyphError {
-    #[error("Failed to open file: {0}")]
-    FileError(std::io::Error),
-    #[error("Memory mapping failed: {0}")]
-    MmapError(std::io::Error),
-    #[error("Failed to load image: {0}")]
-    ImageLoadError(#[from] image::ImageError),
-    #[error("Invalid glyph dimensions: {0}x{1}, expected 128x128")]
-    InvalidDimensions(u32, u32),
-}
-
-#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
-pub struct LongCount {
-    baktun: i32,
-    katun: i32,
-    tun: i32,
-    uinal: i32,
-    kin: i32,
-}
-
-impl LongCount {
-    pub fn from_days(days: i32) -> Self {
-        let baktun = days / 144_000;
-        let rem1 = days % 144_000;
-        let katun = rem1 / 7_200;
-        let rem2 = rem1 % 7_200;
-        let tun = rem2 / 360;
-        let rem3 = rem2 % 360;
-        let uinal = rem3 / 20;
-        let kin = rem3 % 20;
-        Self { baktun, katun, tun, uinal, kin }
-    }
-
-    pub fn to_days(&self) -> i32 {
-        self.baktun * 144_000 +
-        self.katun * 7_200 +
-        self.tun * 360 +
-        self.uinal * 20 +
-        self.kin
-    }
-}
-
-#[derive(Clone)]
-pub struct CalendarData {
-    long_count: LongCount,
-    tzolkin: TzolkinDate,
-    haab: HaabDate,
-    moon_phase: String,
-    venus_phase: String,
-    year_bearer: String,
-    next_solstice: (String, i32),
-    eclipse_status: String,
-    historical_event: Option<String>,
-    gregorian_date: NaiveDate,
-    julian_day_number: i32,
-    days_since_creation: i32,
-}
-
-impl CalendarData {
-    pub fn new(date: NaiveDateTime) -> Self {
-        // Correct Mayan epoch: August 11, 3114 BCE is represented as -3113
-        let mayan_epoch = NaiveDate::from_ymd_opt(-3113, 8, 11)
-            .unwrap()
-            .and_hms_opt(0, 0, 0)
-            .unwrap();
-        let days_since_creation = date.signed_duration_since(mayan_epoch).num_days() as i32;
-        let long_count = LongCount::from_days(days_since_creation);
-        let tzolkin = tzolkin_date(days_since_creation);
-        let haab = haab_date(days_since_creation);
-        Self {
-            long_count,
-            tzolkin,
-            haab,
-            moon_phase: moon_phase(days_since_creation),
-            venus_phase: venus_phase(days_since_creation),
-            year_bearer: year_bearer(days_since_creation),
-            next_solstice: (String::new(), 0),
-            eclipse_status: next_eclipse(days_since_creation),
-            historical_event: historical_event(days_since_creation).map(|s| s.to_string()),
-            gregorian_date: date.date(),
-            julian_day_number: days_since_creation + 584283,
-            days_since_creation,
-        }
-    }
-
-    pub fn new_from_components(
-        long_count: LongCount,
-        tzolkin: TzolkinDate,
-        haab: HaabDate,
-        days: i32,
-    ) -> Self {
-        Self {
-            long_count,
-            tzolkin,
-            haab,
-            moon_phase: String::new(),
-            venus_phase: String::new(),
-            year_bearer: String::new(),
-            next_solstice: (String::new(), 0),
-            eclipse_status: String::new(),
-            historical_event: None,
-            gregorian_date: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
-            julian_day_number: 0,
-            days_since_creation: days,
-        }
-    }
-}
-
-fn to_mayan_numeral_string(long_count: &LongCount) -> String {
-    format!("{}.{}.{}.{}.{}", 
-        to_mayan_digit(long_count.baktun),
-        to_mayan_digit(long_count.katun),
-        to_mayan_digit(long_count.tun),
-        to_mayan_digit(long_count.uinal),
-        to_mayan_digit(long_count.kin))
-}
-
-fn to_mayan_digit(n: i32) -> String {
-    // Define the Unicode code points for Mayan numerals (0-19)
-    let base_codepoint = 0x1D2E0;  // Starting code point for Mayan numerals
-    let codepoint = base_codepoint + (n as u32);
-    // Convert to a char and then to a string, with debug logging
-    match char::from_u32(codepoint) {
-        Some(c) => {
-            tracing::info!("Generated Mayan numeral for {}: U+{:X}", n, codepoint);
-            c.to_string()
-        },
-        None => {
-            tracing::error!("Failed to create Mayan numeral for {}", n);
-            "?".to_string()
-        }
-    }
-}
-
-pub struct GlyphRenderer {
-    cache: Arc<RwLock<TextureCache>>,
-    config: Config,
-    metrics: Arc<Metrics>,
-    ctx: Context, // Egui context
-}
-
-impl GlyphRenderer {
-    pub fn new(ctx: &Context, config: Config) -> Self {
-        Self {
-            cache: Arc::new(RwLock::new(TextureCache {
-                tzolkin_textures: HashMap::new(),
-                haab_textures: HashMap::new(),
-            })),
-            config,
-            metrics: Arc::new(Metrics::new()),
-            ctx: ctx.clone(),
-        }
-    }
-
-    pub fn get_texture(&self, glyph_type: GlyphType, name: &str) -> Option<TextureHandle> {
-        // Get the path from the configuration (assuming config has these fields)
-        let path = match glyph_type {
-            GlyphType::Tzolkin => self.config.tzolkin_glyphs.get(name)?,
-            GlyphType::Haab => self.config.haab_glyphs.get(name)?,
-        };
-        // Check the cache using the file path as the key
-        let mut cache = self.cache.write().unwrap();
-        let cached_texture = match glyph_type {
-            GlyphType::Tzolkin => cache.tzolkin_textures.get(path).cloned(),
-            GlyphType::Haab => cache.haab_textures.get(path).cloned(),
-        };
-        if let Some(texture) = cached_texture {
-            return Some(texture);
-        }
-        // Load image using the image crate
-        let image = match image::open(path) {
-            Ok(img) => {
-                tracing::info!("Successfully loaded glyph image: {}", path);
-                img
-            }
-            Err(e) => {
-                tracing::error!("Failed to load image at {}: {}", path, e);
-                return None;
-            }
-        };
-        let size = [image.width() as usize, image.height() as usize];
-        let image_buffer = image.to_rgba8();
-        let pixels = image_buffer.as_flat_samples();
-        let image_data = ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
-        // Load the texture into egui and cache it using the same key (i.e. file path)
-        let texture = self.ctx.load_texture(name, image_data, TextureOptions::default());
-        match glyph_type {
-            GlyphType::Tzolkin => { cache.tzolkin_textures.insert(path.clone(), texture.clone()); },
-            GlyphType::Haab => { cache.haab_textures.insert(path.clone(), texture.clone()); },
-        }
-        Some(texture)
-    }
-}
-
-pub struct ParallelCalendarCalculator {
-    metrics: Arc<Metrics>,
-    cache: Arc<RwLock<CalendarCache>>,
-}
-
-impl ParallelCalendarCalculator {
-    pub fn new(cache: Arc<RwLock<CalendarCache>>, metrics: Arc<Metrics>) -> Self {
-        Self { metrics, cache }
-    }
-    
-    pub fn calculate_new_data(&self, days: i32) -> CalendarData {
-        let long_count = LongCount::from_days(days);
-        let tzolkin = tzolkin_date(days);
-        let haab = haab_date(days);
-        let mut data = CalendarData::new_from_components(long_count, tzolkin, haab, days);
-        // Populate astronomical and historical data...
-        // (Dummy updates for now)
-        data.moon_phase = moon_phase(days);
-        data.venus_phase = venus_phase(days);
-        data.year_bearer = year_bearer(days);
-        // Use the current date from the system
-        let current_date = chrono::Local::now().naive_local().date();
-        let (solstice, days_to_event) = next_solstice_or_equinox(
-            current_date.year(), 
-            current_date.month() as i32,
-            current_date.day() as i32
-        );
-        data.next_solstice = (solstice, days_to_event);
-        data.eclipse_status = next_eclipse(days);
-        data.historical_event = historical_event(days).map(|s| s.to_string());
-         // Update the Gregorian date to the current date
-        data.gregorian_date = current_date;
-        data.julian_day_number = days + 584283;
-        data
-    }
-}
-
-// ----- MAYAN CALENDAR STRUCT & METHODS -----
-
-pub struct MayanCalendar {
-    current_time: chrono::DateTime<chrono::Local>,
-    calendar_data: CalendarData,
-    last_calendar_update: chrono::NaiveDateTime,
-    cache: Arc<RwLock<CalendarCache>>,
-    glyph_renderer: GlyphRenderer,
-    calculator: ParallelCalendarCalculator,
-    metrics: Arc<Metrics>,
-}
-
-impl MayanCalendar {
-    pub fn new(ctx: &Context) -> Result<Self, Box<dyn std::error::Error>> {
-        let metrics = Arc::new(Metrics::new());
-        let cache = Arc::new(RwLock::new(CalendarCache::new(NonZeroUsize::new(100).unwrap())));
-        let glyph_renderer = GlyphRenderer::new(ctx, Config::default());
-        let now = chrono::Local::now().naive_local();
-        Ok(Self {
-            current_time: chrono::Local::now(),
-            calendar_data: CalendarData::new(now),
-            last_calendar_update: now,
-            cache: Arc::clone(&cache),
-            glyph_renderer,
-            calculator: ParallelCalendarCalculator::new(Arc::clone(&cache), Arc::clone(&metrics)),
-            metrics,
-        })
-    }
-
-    pub fn update_calendar_data(&mut self) {
-        let now = chrono::Local::now();
-        if now != self.current_time {
-            self.current_time = now;
-            self.calendar_data = CalendarData::new(self.current_time.naive_local());
-            // Update astronomical data
-            self.calendar_data.moon_phase = moon_phase(self.calendar_data.days_since_creation);
-            self.calendar_data.venus_phase = venus_phase(self.calendar_data.days_since_creation);
-            self.calendar_data.year_bearer = year_bearer(self.calendar_data.days_since_creation);
-            self.calendar_data.eclipse_status = next_eclipse(self.calendar_data.days_since_creation);
-            tracing::info!(
-                "Updated astronomical info: Moon Phase: {}, Eclipse: {}, Venus: {}, Year Bearer: {}",
-                self.calendar_data.moon_phase,
-                self.calendar_data.eclipse_status,
-                self.calendar_data.venus_phase,
-                self.calendar_data.year_bearer
-            );
-        }
-    }
-
-    pub fn render(&mut self, ctx: &Context) {
-        let desired_size = Vec2::new(128.0, 128.0);
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("Mayan Calendar");
-
-            if let Some(haab_glyph) = self.glyph_renderer.get_texture(
-                GlyphType::Haab,
-                &self.calendar_data.haab.yucatec_month.to_lowercase(),
-            ) {
-                let (rect, _response) = ui.allocate_exact_size(desired_size, egui::Sense::hover());
-                ui.painter().image(
-                    haab_glyph.id(),
-                    rect,
-                    egui::Rect::from_min_max(egui::Pos2::ZERO, egui::Pos2::new(1.0, 1.0)),
-                    egui::Color32::WHITE,
-                );
-            }
-            if let Some(tzolkin_glyph) = self.glyph_renderer.get_texture(
-                GlyphType::Tzolkin,
-                &self.calendar_data.tzolkin.yucatec_name.to_lowercase(),
-            ) {
-                let (rect, _response) = ui.allocate_exact_size(desired_size, egui::Sense::hover());
-                ui.painter().image(
-                    tzolkin_glyph.id(),
-                    rect,
-                    egui::Rect::from_min_max(egui::Pos2::ZERO, egui::Pos2::new(1.0, 1.0)),
-                    egui::Color32::WHITE,
-                );
-            }
-            ui.horizontal(|ui| {
-                let mayan_text = to_mayan_numeral_string(&self.calendar_data.long_count);
-                ui.label(
-                    egui::RichText::new(format!(
-                        "Long Count (Mayan Numerals): {}",
-                        mayan_text
-                    ))
-                    .font(egui::FontId::new(24.0, egui::FontFamily::Monospace))  // Fall back to monospace
-                    .size(24.0)
-                );
-                tracing::info!("Attempting to display Mayan numerals: {}", mayan_text);
-            });
-            ui.separator();
-            ui.label(format!("Current Time: {}", self.current_time.format("%Y-%m-%d %H:%M:%S")));
-            ui.label(format!(
-                "Long Count: {}.{}.{}.{}.{}",
-                self.calendar_data.long_count.baktun,
-                self.calendar_data.long_count.katun,
-                self.calendar_data.long_count.tun,
-                self.calendar_data.long_count.uinal,
-                self.calendar_data.long_count.kin
-            ));
-            ui.label(format!(
-                "Tzolkin: {} {}",
-                self.calendar_data.tzolkin.number,
-                self.calendar_data.tzolkin.yucatec_name
-            ));
-            ui.label(format!(
-                "Haab: {} {}",
-                self.calendar_data.haab.day,
-                self.calendar_data.haab.yucatec_month
-            ));
-            ui.label(format!("Moon Phase: {}", self.calendar_data.moon_phase));
-            ui.label(format!("Eclipse Status: {}", self.calendar_data.eclipse_status));
-            ui.label(format!("Venus Phase: {}", self.calendar_data.venus_phase));
-            ui.label(format!("Year Bearer: {}", self.calendar_data.year_bearer));
-        }); // This closes the egui::CentralPanel::default().show block
-    }
-} // This closes the impl MayanCalendar block
-
-// --------- MAYAN CALENDAR APP METHOD ----------
-
-impl App for MayanCalendar {
-    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
-        if (chrono::Local::now() - self.current_time).num_seconds() >= 1 {
-            self.update_calendar_data();
-        }
-        self.render(ctx);
-        ctx.request_repaint_after(std::time::Duration::from_secs(1));
-    }
-}
-
-// ----- CONFIGURE FONTS & MAIN -----
-
-fn configure_fonts(ctx: &Context) -> Result<(), Box<dyn std::error::Error>> {
-    let mut fonts = egui::FontDefinitions::default();
-    // Load font with corrected path and add debug logging
-    let font_data = include_bytes!("../assets/fonts/NotoSansMayanNumerals-Regular.ttf");
-    tracing::info!("Font data loaded successfully, size: {} bytes", font_data.len());
-    // Add font with a unique name
-    fonts.font_data.insert(
-        "mayan_numerals".to_owned(),
-        egui::FontData::from_static(font_data)
-    );
-    // Register for both Proportional and Monospace families
-    fonts.families.get_mut(&egui::FontFamily::Proportional)
-        .unwrap()
-        .insert(0, "mayan_numerals".to_owned());
-    
-    fonts.families.get_mut(&egui::FontFamily::Monospace)
-        .unwrap()
-        .insert(0, "mayan_numerals".to_owned());
-    // Create a dedicated Mayan family
-    fonts.families.insert(
-        egui::FontFamily::Name("mayan".into()),
-        vec!["mayan_numerals".to_owned()]
-    );
-    ctx.set_fonts(fonts);
-    tracing::info!("Font configuration completed");
-    Ok(())
-}
-
-// ---------- MAIN FUNCTION ----------
-
-fn main() -> Result<(), eframe::Error> {
-    // Initialize logging
-    tracing_subscriber::FmtSubscriber::builder()
-        .with_env_filter(EnvFilter::from_default_env().add_directive(Level::INFO.into()))
-        .with_thread_ids(true)
-        .with_thread_names(true)
-        .with_file(true)
-        .with_line_number(true)
-        .with_target(false)
-        .compact()
-        .init();
-    // Set up application options
-    let options = NativeOptions {
-        viewport: ViewportBuilder::default().with_inner_size([800.0, 600.0]),
-        vsync: true,
-        ..Default::default()
-    };
-    // Run the application
-    eframe::run_native(
-        "Mayan Calendar",
-        options,
-        Box::new(|cc| {
-            // Configure fonts before creating the app
-            configure_fonts(&cc.egui_ctx).expect("Failed to configure fonts");
-                 let app = MayanCalendar::new(&cc.egui_ctx).unwrap();
-            Box::new(app)
-        }),
-    )
-}
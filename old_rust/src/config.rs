@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::correlation::Correlation;
+use crate::date_utils::{HaabNumbering, Timezone, HAAB_MONTHS, TZOLKIN_NAMES};
+
+/// Glyph directories, relative to the asset root
+pub const TZOLKIN_GLYPH_PATH: &str = "tzolkin/glyphs";
+pub const HAAB_GLYPH_PATH: &str = "haab/glyphs";
+pub const NIGHT_GLYPH_PATH: &str = "night/glyphs";
+/// Optional sprite sheet holding every glyph, and the JSON manifest locating each one in it
+pub const ATLAS_IMAGE_PATH: &str = "glyphs/atlas.png";
+pub const ATLAS_MANIFEST_PATH: &str = "glyphs/atlas.json";
+
+/// Environment variable overriding where assets are looked up
+pub const ASSET_DIR_ENV: &str = "MAYAN_ASSETS";
+/// Environment variable naming a TOML config file to load
+pub const CONFIG_FILE_ENV: &str = "MAYAN_CONFIG";
+
+/// Why a config file couldn't be loaded
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Failed to read config file: {0}")]
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "config-file")]
+    #[error("Invalid config file: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Unknown correlation {0:?} (expected gmt, gmt+2, lounsbury, bohm or a JDN)")]
+    UnknownCorrelation(String),
+    #[error("Unknown Haab' numbering {0:?} (expected seating or one-based)")]
+    UnknownHaabNumbering(String),
+    #[error("Unknown timezone {0:?} (expected local, utc or an offset in hours)")]
+    UnknownTimezone(String),
+    #[error("Config files are not supported in this build")]
+    Unsupported,
+}
+
+// The parts of a config file; anything left out keeps its default
+#[cfg(feature = "config-file")]
+#[derive(serde::Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    asset_root: Option<PathBuf>,
+    correlation: Option<CorrelationSetting>,
+    haab_numbering: Option<String>,
+    timezone: Option<TimezoneSetting>,
+    cache_file: Option<PathBuf>,
+    tzolkin_glyphs: HashMap<String, PathBuf>,
+    haab_glyphs: HashMap<String, PathBuf>,
+}
+
+// A correlation by name, or the JDN of 0.0.0.0.0 itself
+#[cfg(feature = "config-file")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum CorrelationSetting {
+    Jdn(i32),
+    Name(String),
+}
+
+#[cfg(feature = "config-file")]
+impl CorrelationSetting {
+    fn resolve(self) -> Result<Correlation, ConfigError> {
+        let name = match self {
+            CorrelationSetting::Jdn(jdn) => return Ok(Correlation::Custom(jdn)),
+            CorrelationSetting::Name(name) => name,
+        };
+        match name.to_ascii_lowercase().as_str() {
+            "gmt" => Ok(Correlation::GMT),
+            "gmt+2" => Ok(Correlation::GMTPlus2),
+            "lounsbury" => Ok(Correlation::Lounsbury),
+            "bohm" | "böhm" => Ok(Correlation::Bohm),
+            _ => Err(ConfigError::UnknownCorrelation(name)),
+        }
+    }
+}
+
+#[cfg(feature = "config-file")]
+fn resolve_haab_numbering(name: String) -> Result<HaabNumbering, ConfigError> {
+    match name.to_ascii_lowercase().as_str() {
+        "seating" => Ok(HaabNumbering::Seating),
+        "one-based" => Ok(HaabNumbering::OneBased),
+        _ => Err(ConfigError::UnknownHaabNumbering(name)),
+    }
+}
+
+// "local", "utc", or a fixed offset from UTC in hours
+#[cfg(feature = "config-file")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum TimezoneSetting {
+    Offset(i32),
+    Name(String),
+}
+
+#[cfg(feature = "config-file")]
+impl TimezoneSetting {
+    fn resolve(self) -> Result<Timezone, ConfigError> {
+        let name = match self {
+            TimezoneSetting::Offset(hours) => return Ok(Timezone::UtcOffset(hours)),
+            TimezoneSetting::Name(name) => name,
+        };
+        match name.to_ascii_lowercase().as_str() {
+            "local" => Ok(Timezone::Local),
+            "utc" => Ok(Timezone::UtcOffset(0)),
+            _ => Err(ConfigError::UnknownTimezone(name)),
+        }
+    }
+}
+
+pub struct Config {
+    pub asset_root: PathBuf,
+    pub tzolkin_glyphs: HashMap<String, PathBuf>,
+    pub haab_glyphs: HashMap<String, PathBuf>,
+    /// Lords of the Night, keyed "g1" to "g9"
+    pub night_glyphs: HashMap<String, PathBuf>,
+    pub atlas_image: PathBuf,
+    pub atlas_manifest: PathBuf,
+    pub correlation: Correlation,
+    /// Whether Haab' days are shown 0-19 (seating) or 1-20
+    pub haab_numbering: HaabNumbering,
+    /// Where the day rolls over when working out today's date
+    pub timezone: Timezone,
+    /// File the GUI keeps its calendar cache in between runs
+    pub cache_file: Option<PathBuf>,
+}
+
+/// `$MAYAN_ASSETS` if set, else an `assets` directory next to the executable,
+/// else `assets` relative to the working directory
+pub fn default_asset_root() -> PathBuf {
+    if let Some(dir) = std::env::var_os(ASSET_DIR_ENV) {
+        return PathBuf::from(dir);
+    }
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.join("assets")))
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(|| PathBuf::from("assets"))
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::with_asset_root(default_asset_root())
+    }
+}
+
+impl Config {
+    pub fn new() -> Self {
+        // This is essentially the same as the default implementation
+        Self::default()
+    }
+
+    /// Config with every glyph path resolved under `asset_root`
+    pub fn with_asset_root(asset_root: impl Into<PathBuf>) -> Self {
+        let asset_root = asset_root.into();
+        let tzolkin_dir = asset_root.join(TZOLKIN_GLYPH_PATH);
+        let haab_dir = asset_root.join(HAAB_GLYPH_PATH);
+        let night_dir = asset_root.join(NIGHT_GLYPH_PATH);
+        let mut tzolkin_glyphs = HashMap::new();
+        let mut haab_glyphs = HashMap::new();
+
+        // Tzolk'in day glyphs with traditional Maya spellings
+        tzolkin_glyphs.insert("imix".to_string(), tzolkin_dir.join("imix.png"));
+        tzolkin_glyphs.insert("ik'".to_string(), tzolkin_dir.join("ik.png"));
+        tzolkin_glyphs.insert("ak'b'al".to_string(), tzolkin_dir.join("akbal.png"));
+        tzolkin_glyphs.insert("k'an".to_string(), tzolkin_dir.join("kan.png"));
+        tzolkin_glyphs.insert("chikchan".to_string(), tzolkin_dir.join("chikchan.png"));
+        tzolkin_glyphs.insert("kimi".to_string(), tzolkin_dir.join("kimi.png"));
+        tzolkin_glyphs.insert("manik'".to_string(), tzolkin_dir.join("manik.png"));
+        tzolkin_glyphs.insert("lamat".to_string(), tzolkin_dir.join("lamat.png"));
+        tzolkin_glyphs.insert("muluk".to_string(), tzolkin_dir.join("muluk.png"));
+        tzolkin_glyphs.insert("ok".to_string(), tzolkin_dir.join("ok.png"));
+        tzolkin_glyphs.insert("chuwen".to_string(), tzolkin_dir.join("chuwen.png"));
+        tzolkin_glyphs.insert("eb'".to_string(), tzolkin_dir.join("eb.png"));
+        tzolkin_glyphs.insert("b'en".to_string(), tzolkin_dir.join("ben.png"));
+        tzolkin_glyphs.insert("ix".to_string(), tzolkin_dir.join("ix.png"));
+        tzolkin_glyphs.insert("men".to_string(), tzolkin_dir.join("men.png"));
+        tzolkin_glyphs.insert("kib'".to_string(), tzolkin_dir.join("kib.png"));
+        tzolkin_glyphs.insert("kab'an".to_string(), tzolkin_dir.join("kaban.png"));
+        tzolkin_glyphs.insert("etz'nab'".to_string(), tzolkin_dir.join("etznab.png"));
+        tzolkin_glyphs.insert("kawak".to_string(), tzolkin_dir.join("kawak.png"));
+        tzolkin_glyphs.insert("ajaw".to_string(), tzolkin_dir.join("ajaw.png"));
+
+        // Haab' month glyphs, keyed by Yucatec name; the shipped files are
+        // named with the highland spellings (Siq' for Sip, Sotj for Sotz', ...)
+        haab_glyphs.insert("pop".to_string(), haab_dir.join("pop.png"));
+        haab_glyphs.insert("wo'".to_string(), haab_dir.join("wo.png"));
+        haab_glyphs.insert("sip".to_string(), haab_dir.join("siq.png"));
+        haab_glyphs.insert("sotz'".to_string(), haab_dir.join("sotj.png"));
+        haab_glyphs.insert("sek".to_string(), haab_dir.join("soxj.png"));
+        haab_glyphs.insert("xul".to_string(), haab_dir.join("xul.png"));
+        haab_glyphs.insert("yaxk'in".to_string(), haab_dir.join("yaxin.png"));
+        haab_glyphs.insert("mol".to_string(), haab_dir.join("mal.png"));
+        haab_glyphs.insert("ch'en".to_string(), haab_dir.join("chen.png"));
+        haab_glyphs.insert("yax".to_string(), haab_dir.join("yax.png"));
+        haab_glyphs.insert("sak'".to_string(), haab_dir.join("sax.png"));
+        haab_glyphs.insert("keh".to_string(), haab_dir.join("koh.png"));
+        haab_glyphs.insert("mak".to_string(), haab_dir.join("max.png"));
+        haab_glyphs.insert("k'ank'in".to_string(), haab_dir.join("kanxin.png"));
+        haab_glyphs.insert("muwan".to_string(), haab_dir.join("muwan.png"));
+        haab_glyphs.insert("pax".to_string(), haab_dir.join("pax.png"));
+        haab_glyphs.insert("k'ayab".to_string(), haab_dir.join("kayab.png"));
+        haab_glyphs.insert("kumk'u".to_string(), haab_dir.join("kunxu.png"));
+        haab_glyphs.insert("wayeb'".to_string(), haab_dir.join("wayeb.png"));
+
+        // Lords of the Night G1-G9
+        let night_glyphs = (1..=9)
+            .map(|g| (format!("g{}", g), night_dir.join(format!("g{}.png", g))))
+            .collect();
+
+        Self {
+            atlas_image: asset_root.join(ATLAS_IMAGE_PATH),
+            atlas_manifest: asset_root.join(ATLAS_MANIFEST_PATH),
+            asset_root,
+            tzolkin_glyphs,
+            haab_glyphs,
+            night_glyphs,
+            correlation: Correlation::default(),
+            haab_numbering: HaabNumbering::default(),
+            timezone: Timezone::default(),
+            cache_file: None,
+        }
+    }
+
+    /// Reads a TOML config file. `asset_root`, `correlation`, `haab_numbering`,
+    /// `timezone` and `cache_file` are top-level keys, and the
+    /// `[tzolkin_glyphs]` and `[haab_glyphs]` tables add to or replace the
+    /// default glyph entries; relative glyph paths are taken from the asset
+    /// root. Anything the file leaves out keeps its default.
+    #[cfg(feature = "config-file")]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Config, ConfigError> {
+        let file: ConfigFile = toml::from_str(&std::fs::read_to_string(path)?)?;
+        let mut config = Self::with_asset_root(file.asset_root.unwrap_or_else(default_asset_root));
+        if let Some(correlation) = file.correlation {
+            config.correlation = correlation.resolve()?;
+        }
+        if let Some(numbering) = file.haab_numbering {
+            config.haab_numbering = resolve_haab_numbering(numbering)?;
+        }
+        if let Some(timezone) = file.timezone {
+            config.timezone = timezone.resolve()?;
+        }
+        config.cache_file = file.cache_file;
+        for (glyphs, overrides) in [
+            (&mut config.tzolkin_glyphs, file.tzolkin_glyphs),
+            (&mut config.haab_glyphs, file.haab_glyphs),
+        ] {
+            for (name, path) in overrides {
+                glyphs.insert(name, config.asset_root.join(path));
+            }
+        }
+        Ok(config)
+    }
+
+    #[cfg(not(feature = "config-file"))]
+    pub fn from_file(_path: impl AsRef<Path>) -> Result<Config, ConfigError> {
+        Err(ConfigError::Unsupported)
+    }
+
+    /// The file named by `$MAYAN_CONFIG` if set, else the defaults
+    pub fn load() -> Result<Config, ConfigError> {
+        match std::env::var_os(CONFIG_FILE_ENV) {
+            Some(path) => Self::from_file(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Checks that every Tzolk'in day and Haab' month has a glyph entry under
+    /// its lowercased name pointing at a file that exists, and that there are
+    /// no other entries. Each problem found is one message.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = glyph_key_problems("Tzolk'in", &self.tzolkin_glyphs, &TZOLKIN_NAMES);
+        problems.extend(glyph_key_problems("Haab'", &self.haab_glyphs, &HAAB_MONTHS));
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+// Canonical names with no glyph, then keys that aren't canonical names
+fn glyph_key_problems(kind: &str, glyphs: &HashMap<String, PathBuf>, names: &[&str]) -> Vec<String> {
+    let expected: Vec<String> = names.iter().map(|name| name.to_lowercase()).collect();
+    let mut problems: Vec<String> = names
+        .iter()
+        .zip(&expected)
+        .filter(|(_, key)| !glyphs.contains_key(*key))
+        .map(|(name, _)| format!("No {} glyph for {}", kind, name))
+        .collect();
+    let mut extra: Vec<&String> = glyphs.keys().filter(|key| !expected.contains(key)).collect();
+    extra.sort();
+    problems.extend(extra.into_iter().map(|key| format!("Unknown {} glyph key {:?}", kind, key)));
+    problems.extend(
+        names
+            .iter()
+            .zip(&expected)
+            .filter_map(|(name, key)| Some((name, glyphs.get(key)?)))
+            .filter(|(_, path)| !path.is_file())
+            .map(|(name, path)| format!("Missing {} glyph file for {}: {}", kind, name, path.display())),
+    );
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_glyph_paths_follow_asset_root() {
+        let config = Config::with_asset_root("/opt/maya");
+        assert_eq!(config.asset_root, Path::new("/opt/maya"));
+        assert_eq!(config.tzolkin_glyphs["ajaw"], Path::new("/opt/maya/tzolkin/glyphs/ajaw.png"));
+        assert_eq!(config.haab_glyphs["pop"], Path::new("/opt/maya/haab/glyphs/pop.png"));
+        // Sotj is the bat head of Sotz', Soxj the cartouche of Sek
+        assert_eq!(config.haab_glyphs["sotz'"], Path::new("/opt/maya/haab/glyphs/sotj.png"));
+        assert_eq!(config.haab_glyphs["sek"], Path::new("/opt/maya/haab/glyphs/soxj.png"));
+        assert_eq!(config.night_glyphs.len(), 9);
+        assert_eq!(config.night_glyphs["g9"], Path::new("/opt/maya/night/glyphs/g9.png"));
+    }
+
+    #[test]
+    fn test_validate() {
+        // Every default entry points at a file shipped in assets/
+        let mut config = Config::with_asset_root(Path::new(env!("CARGO_MANIFEST_DIR")).join("assets"));
+        assert_eq!(config.validate(), Ok(()));
+
+        let path = config.haab_glyphs.remove("sotz'").unwrap();
+        config.haab_glyphs.insert("soxj'".to_string(), path);
+        config.tzolkin_glyphs.remove("ajaw");
+        assert_eq!(
+            config.validate(),
+            Err(vec![
+                "No Tzolk'in glyph for Ajaw".to_string(),
+                "No Haab' glyph for Sotz'".to_string(),
+                "Unknown Haab' glyph key \"soxj'\"".to_string(),
+            ])
+        );
+
+        let mut config = Config::with_asset_root("/nonexistent");
+        let shipped = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/tzolkin/glyphs/ajaw.png");
+        config.tzolkin_glyphs.insert("ajaw".to_string(), shipped);
+        let problems = config.validate().unwrap_err();
+        assert_eq!(problems.len(), 19 + 19);
+        assert_eq!(problems[0], "Missing Tzolk'in glyph file for Imix: /nonexistent/tzolkin/glyphs/imix.png");
+        assert!(!problems.iter().any(|problem| problem.contains("Ajaw")));
+    }
+
+    #[cfg(feature = "config-file")]
+    #[test]
+    fn test_from_file() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/mayan_calendar.toml");
+        let config = Config::from_file(path).unwrap();
+        assert_eq!(config.asset_root, Path::new("/opt/maya"));
+        assert_eq!(config.correlation, Correlation::Lounsbury);
+        assert_eq!(config.haab_numbering, HaabNumbering::OneBased);
+        assert_eq!(config.timezone, Timezone::UtcOffset(-6));
+        assert_eq!(config.cache_file.as_deref(), Some(Path::new("/var/cache/mayan_calendar.json")));
+        assert_eq!(config.tzolkin_glyphs["ajaw"], Path::new("/opt/maya/custom/ajaw.svg"));
+        assert_eq!(config.haab_glyphs["k'ayab"], Path::new("/srv/glyphs/kayab.png"));
+        // Entries the file doesn't mention keep their defaults under its asset root
+        assert_eq!(config.tzolkin_glyphs["imix"], Path::new("/opt/maya/tzolkin/glyphs/imix.png"));
+        // Every key is right; only the files are missing on this machine
+        assert!(config.validate().unwrap_err().iter().all(|problem| problem.starts_with("Missing ")));
+
+        let write = |name: &str, contents: &str| {
+            let path = std::env::temp_dir().join(format!("mayan_calendar_{}_{}.toml", name, std::process::id()));
+            std::fs::write(&path, contents).unwrap();
+            path
+        };
+        let jdn = write("jdn", "correlation = 584285\n");
+        assert_eq!(Config::from_file(&jdn).unwrap().correlation, Correlation::Custom(584285));
+        let unknown = write("unknown", "correlation = \"spinden\"\n");
+        assert!(matches!(Config::from_file(&unknown), Err(ConfigError::UnknownCorrelation(_))));
+        let names = write("names", "haab_numbering = \"Seating\"\ntimezone = \"utc\"\n");
+        let config = Config::from_file(&names).unwrap();
+        assert_eq!(config.haab_numbering, HaabNumbering::Seating);
+        assert_eq!(config.timezone, Timezone::UtcOffset(0));
+        let numbering = write("numbering", "haab_numbering = \"zero-based\"\n");
+        assert!(matches!(Config::from_file(&numbering), Err(ConfigError::UnknownHaabNumbering(_))));
+        let timezone = write("timezone", "timezone = \"America/Merida\"\n");
+        assert!(matches!(Config::from_file(&timezone), Err(ConfigError::UnknownTimezone(_))));
+        let typo = write("typo", "[tzolkin_glyph]\najaw = \"a.png\"\n");
+        assert!(matches!(Config::from_file(&typo), Err(ConfigError::Parse(_))));
+        for path in [jdn, unknown, names, numbering, timezone, typo] {
+            std::fs::remove_file(path).unwrap();
+        }
+        assert!(matches!(Config::from_file("/nonexistent/mayan.toml"), Err(ConfigError::Io(_))));
+    }
+}
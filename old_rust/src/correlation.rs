@@ -0,0 +1,161 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::date_utils::{gregorian_to_jdn, CalendarRound, DateError};
+use crate::long_count::LongCount;
+
+/// Correlation constants tying the Maya creation date (0.0.0.0.0) to a Julian Day Number.
+///
+/// Scholars disagree on the exact alignment, so every conversion between the
+/// Long Count and the Julian Day Number goes through one of these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Correlation {
+    /// Goodman-Martinez-Thompson correlation (JDN 584283)
+    #[default]
+    GMT,
+    /// The "astronomical" GMT variant, two days later (JDN 584285)
+    GMTPlus2,
+    /// Lounsbury's correlation based on the Dresden Codex Venus table (JDN 584285)
+    Lounsbury,
+    /// Böhm & Böhm correlation (JDN 622261)
+    Bohm,
+    /// Any other correlation, given directly as the JDN of 0.0.0.0.0
+    Custom(i32),
+}
+
+impl Correlation {
+    /// Returns the Julian Day Number of the creation date under this correlation
+    pub fn jdn_offset(&self) -> i32 {
+        match self {
+            Correlation::GMT => 584283,
+            Correlation::GMTPlus2 => 584285,
+            Correlation::Lounsbury => 584285,
+            Correlation::Bohm => 622261,
+            Correlation::Custom(offset) => *offset,
+        }
+    }
+}
+
+/// The published correlations with their names, for showing how far apart
+/// they put the same day
+pub const NAMED_CORRELATIONS: [(&str, i32); 4] = [
+    ("GMT", 584283),
+    ("GMT+2", 584285),
+    ("Lounsbury", 584285),
+    ("Böhm", 622261),
+];
+
+/// The Long Count and Calendar Round of `date` under each named correlation,
+/// in the order given
+pub fn compare_correlations(date: NaiveDate, correlations: &[(&str, i32)]) -> Vec<(String, LongCount, CalendarRound)> {
+    let jdn = gregorian_to_jdn(date.year(), date.month() as i32, date.day() as i32);
+    correlations
+        .iter()
+        .map(|&(name, correlation)| {
+            let days = jdn - correlation;
+            (name.to_string(), LongCount::from_days(days), CalendarRound::from_days(days))
+        })
+        .collect()
+}
+
+/// Converts a Julian Day Number to a (proleptic) Gregorian date, or
+/// `DateError::JulianDay` past the years chrono can represent (about ±262000)
+pub fn jdn_to_gregorian(jdn: i32) -> Result<NaiveDate, DateError> {
+    let j = jdn as i64 + 32044;
+    // Floor division keeps the remainders non-negative before 4801 BCE
+    let g = j.div_euclid(146097);
+    let dg = j.rem_euclid(146097);
+    let c = (dg / 36524 + 1) * 3 / 4;
+    let dc = dg - c * 36524;
+    let b = dc / 1461;
+    let db = dc % 1461;
+    let a = (db / 365 + 1) * 3 / 4;
+    let da = db - a * 365;
+    let y = g * 400 + c * 100 + b * 4 + a;
+    let m = (da * 5 + 308) / 153 - 2;
+    let d = da - (m + 4) * 153 / 5 + 122;
+    let year = y - 4800 + (m + 2) / 12;
+    let month = ((m + 2) % 12) + 1;
+    let day = d + 1;
+
+    i32::try_from(year)
+        .ok()
+        .and_then(|year| NaiveDate::from_ymd_opt(year, month as u32, day as u32))
+        .ok_or(DateError::JulianDay(jdn as i64))
+}
+
+/// The Gregorian date of a day counted from 0.0.0.0.0, where `correlation`
+/// is the JDN of 0.0.0.0.0
+pub fn days_to_gregorian(days: i32, correlation: i32) -> Result<NaiveDate, DateError> {
+    let jdn = days as i64 + correlation as i64;
+    let jdn = i32::try_from(jdn).map_err(|_| DateError::JulianDay(jdn))?;
+    jdn_to_gregorian(jdn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date_utils::calendar_round_string;
+
+    #[test]
+    fn test_jdn_gregorian_round_trip() {
+        // chrono counts days from 1 January 1 CE, which is JDN 1721426
+        let mut date = NaiveDate::from_ymd_opt(-6000, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(4000, 12, 31).unwrap();
+        while date <= end {
+            let jdn = gregorian_to_jdn(date.year(), date.month() as i32, date.day() as i32);
+            assert_eq!(jdn, date.num_days_from_ce() + 1721425, "{}", date);
+            assert_eq!(jdn_to_gregorian(jdn), Ok(date));
+            date += chrono::Duration::days(97);
+        }
+
+        // Every day across the leap days of 1600, 1900 and 2000
+        for year in [1600, 1900, 2000] {
+            let start = gregorian_to_jdn(year, 2, 20);
+            for jdn in start..start + 20 {
+                let date = jdn_to_gregorian(jdn).unwrap();
+                assert_eq!(gregorian_to_jdn(date.year(), date.month() as i32, date.day() as i32), jdn);
+            }
+        }
+    }
+
+    #[test]
+    fn test_jdn_to_gregorian_out_of_range() {
+        let last = NaiveDate::MAX;
+        let last_jdn = gregorian_to_jdn(last.year(), last.month() as i32, last.day() as i32);
+        assert_eq!(jdn_to_gregorian(last_jdn), Ok(last));
+        assert_eq!(jdn_to_gregorian(last_jdn + 1), Err(DateError::JulianDay(last_jdn as i64 + 1)));
+        let first = NaiveDate::MIN;
+        let first_jdn = gregorian_to_jdn(first.year(), first.month() as i32, first.day() as i32);
+        assert_eq!(jdn_to_gregorian(first_jdn), Ok(first));
+        assert!(jdn_to_gregorian(first_jdn - 1).is_err());
+        assert!(jdn_to_gregorian(i32::MAX).is_err());
+        assert!(jdn_to_gregorian(i32::MIN).is_err());
+
+        assert_eq!(days_to_gregorian(0, 584283), Ok(NaiveDate::from_ymd_opt(-3113, 8, 11).unwrap()));
+        assert_eq!(days_to_gregorian(i32::MAX, 584283), Err(DateError::JulianDay(i32::MAX as i64 + 584283)));
+    }
+
+    #[test]
+    fn test_compare_correlations() {
+        let date = NaiveDate::from_ymd_opt(2012, 12, 21).unwrap();
+        let compared = compare_correlations(date, &NAMED_CORRELATIONS);
+        let shown: Vec<(String, String, String)> = compared
+            .iter()
+            .map(|(name, lc, cr)| (name.clone(), lc.to_string(), calendar_round_string(&cr.tzolkin, &cr.haab)))
+            .collect();
+        let row = |name: &str, lc: &str, cr: &str| (name.to_string(), lc.to_string(), cr.to_string());
+        assert_eq!(
+            shown,
+            vec![
+                row("GMT", "13.0.0.0.0", "4 Ajaw 3 K'ank'in"),
+                row("GMT+2", "12.19.19.17.18", "2 Etz'nab' 1 K'ank'in"),
+                row("Lounsbury", "12.19.19.17.18", "2 Etz'nab' 1 K'ank'in"),
+                row("Böhm", "12.14.14.9.2", "12 Ik' 5 Mak"),
+            ]
+        );
+        for (name, correlation) in NAMED_CORRELATIONS {
+            assert!(compared.iter().any(|(n, lc, _)| n == name && lc.to_days() == 2456283 - correlation));
+        }
+        assert!(compare_correlations(date, &[]).is_empty());
+    }
+}
@@ -0,0 +1,1135 @@
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Utc};
+
+use crate::correlation::{days_to_gregorian, jdn_to_gregorian};
+use crate::names::{day_name, NameSet};
+
+pub fn gregorian_to_jdn(year: i32, month: i32, day: i32) -> i32 {
+// Convert a proleptic Gregorian date to Julian Day Number (JDN).
+// Years use astronomical numbering (1 BCE = 0, 3114 BCE = -3113); floor
+// division keeps the leap-year terms right when y goes negative.
+  let a = (14 - month) / 12;
+  let y = year + 4800 - a;
+  let m = month + 12 * a - 3;
+  day + ((153 * m + 2) / 5) + 365 * y + y.div_euclid(4) - y.div_euclid(100) + y.div_euclid(400) - 32045
+}
+
+/// Convert a Julian-calendar date to Julian Day Number, with the same
+/// astronomical year numbering as `gregorian_to_jdn`
+pub fn julian_to_jdn(year: i32, month: i32, day: i32) -> i32 {
+    let a = (14 - month) / 12;
+    let y = year + 4800 - a;
+    let m = month + 12 * a - 3;
+    day + ((153 * m + 2) / 5) + 365 * y + y.div_euclid(4) - 32083
+}
+
+/// Which calendar a year/month/day was written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CalendarSystem {
+    /// Proleptic Gregorian throughout
+    #[default]
+    Gregorian,
+    /// Julian throughout
+    Julian,
+    /// Julian up to 4 October 1582 and Gregorian from 15 October 1582, as in
+    /// Spanish records of the contact period. The ten days between don't exist.
+    Historical,
+}
+
+impl CalendarSystem {
+    /// Julian Day Number of a date written in this calendar
+    pub fn to_jdn(self, year: i32, month: i32, day: i32) -> i32 {
+        match self {
+            CalendarSystem::Gregorian => gregorian_to_jdn(year, month, day),
+            CalendarSystem::Julian => julian_to_jdn(year, month, day),
+            CalendarSystem::Historical if (year, month, day) < (1582, 10, 15) => julian_to_jdn(year, month, day),
+            CalendarSystem::Historical => gregorian_to_jdn(year, month, day),
+        }
+    }
+
+    /// The proleptic Gregorian date of a day written in this calendar, or an
+    /// error if there is no such day in it (e.g. 5-14 October 1582 under
+    /// `Historical`, or 29 February 1500 under `Gregorian`)
+    pub fn to_gregorian(self, year: i32, month: i32, day: i32) -> Result<NaiveDate, DateError> {
+        let julian = self == CalendarSystem::Julian
+            || (self == CalendarSystem::Historical && (year, month, day) < (1582, 10, 15));
+        if !julian {
+            return checked_gregorian(year, month, day);
+        }
+        let leap = year.rem_euclid(4) == 0;
+        let month_length = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        let skipped = self == CalendarSystem::Historical && (year, month, day) >= (1582, 10, 5);
+        if !(1..=12).contains(&month) || !(1..=month_length).contains(&day) || skipped {
+            return Err(DateError::Julian { year, month, day });
+        }
+        jdn_to_gregorian(julian_to_jdn(year, month, day))
+    }
+
+    /// Parses "YYYY-MM-DD" (the year may be negative, astronomical numbering)
+    /// as a date in this calendar, giving its proleptic Gregorian date
+    pub fn parse_date(self, s: &str) -> Result<NaiveDate, DateError> {
+        let s = s.trim();
+        let (sign, digits) = match s.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let mut fields = digits.split('-').map(|field| field.parse::<i32>());
+        match (fields.next(), fields.next(), fields.next(), fields.next()) {
+            (Some(Ok(year)), Some(Ok(month)), Some(Ok(day)), None) => self.to_gregorian(sign * year, month, day),
+            _ => Err(DateError::Format(s.to_string())),
+        }
+    }
+}
+
+/// Where "today" is reckoned. Maya days are taken to run from local midnight
+/// to midnight, so the date rolls over at midnight in this zone, never at UTC
+/// midnight unless that is the zone chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Timezone {
+    /// The system's local time zone
+    #[default]
+    Local,
+    /// A fixed offset from UTC in whole hours, clamped to -23..=23
+    UtcOffset(i32),
+}
+
+impl Timezone {
+    /// The current wall-clock time in this zone
+    pub fn now(self) -> NaiveDateTime {
+        match self {
+            Timezone::Local => chrono::Local::now().naive_local(),
+            Timezone::UtcOffset(hours) => Utc::now().naive_utc() + Duration::hours(hours.clamp(-23, 23) as i64),
+        }
+    }
+
+    /// Days since 0.0.0.0.0 for today in this zone
+    pub fn today_days(self, correlation: i32) -> i32 {
+        let today = self.now().date();
+        gregorian_to_jdn(today.year(), today.month() as i32, today.day() as i32) - correlation
+    }
+}
+
+/// Days since 0.0.0.0.0 for the current date `tz_offset_hours` from UTC
+pub fn today_days(tz_offset_hours: i32, correlation: i32) -> i32 {
+    Timezone::UtcOffset(tz_offset_hours).today_days(correlation)
+}
+
+/// Why a Tzolk'in, Haab' or Gregorian date was rejected
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum DateError {
+    #[error("Tzolk'in number {0} out of range (1-13)")]
+    TzolkinNumber(i32),
+    #[error("Unknown Tzolk'in day name: {0:?}")]
+    TzolkinName(String),
+    #[error("Haab' day {day} out of range for {month} (0-{max})")]
+    HaabDay { day: i32, month: String, max: i32 },
+    #[error("Unknown Haab' month: {0:?}")]
+    HaabMonth(String),
+    #[error("Invalid Gregorian date {year}-{month:02}-{day:02}")]
+    Gregorian { year: i32, month: i32, day: i32 },
+    #[error("Julian Day Number {0} is outside the supported Gregorian range")]
+    JulianDay(i64),
+    #[error("Invalid Julian date {year}-{month:02}-{day:02}")]
+    Julian { year: i32, month: i32, day: i32 },
+    #[error("Expected a YYYY-MM-DD date, found {0:?}")]
+    Format(String),
+}
+
+/// The proleptic Gregorian date for `year`, `month` and `day`, or
+/// `DateError::Gregorian` if there is no such day (or chrono can't represent it)
+pub fn checked_gregorian(year: i32, month: i32, day: i32) -> Result<NaiveDate, DateError> {
+    u32::try_from(month)
+        .ok()
+        .zip(u32::try_from(day).ok())
+        .and_then(|(m, d)| NaiveDate::from_ymd_opt(year, m, d))
+        .ok_or(DateError::Gregorian { year, month, day })
+}
+
+/// Offsets placing the creation date (0.0.0.0.0) on 4 Ajaw:
+/// the number cycle starts at 4 (index 3) and Ajaw is the 20th day name (index 19)
+pub const TZOLKIN_NUMBER_OFFSET: i32 = 3;
+pub const TZOLKIN_NAME_OFFSET: i32 = 19;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TzolkinDate {
+    pub number: i32,
+    pub yucatec_name: String,
+}
+
+impl TzolkinDate {
+    pub fn new(number: i32, name: &str) -> Self {
+        Self {
+            number,
+            yucatec_name: name.to_string(),
+        }
+    }
+
+    /// Like `new`, but only for numbers 1-13 and the Yucatec day names in `TZOLKIN_NAMES`
+    pub fn try_new(number: i32, name: &str) -> Result<Self, DateError> {
+        if !(1..=13).contains(&number) {
+            return Err(DateError::TzolkinNumber(number));
+        }
+        if !TZOLKIN_NAMES.contains(&name) {
+            return Err(DateError::TzolkinName(name.to_string()));
+        }
+        Ok(Self::new(number, name))
+    }
+
+    /// Position in the 13-day trecena (1-13), which is the day number itself
+    pub fn trecena(&self) -> i32 {
+        self.number
+    }
+
+    /// The number is 1-13 and the name is one of `TZOLKIN_NAMES`
+    pub fn is_valid(&self) -> bool {
+        (1..=13).contains(&self.number) && TZOLKIN_NAMES.contains(&self.yucatec_name.as_str())
+    }
+
+    /// Position in the 20-day veintena (0 for Imix to 19 for Ajaw), or None
+    /// for a name not in `TZOLKIN_NAMES`
+    pub fn veintena_position(&self) -> Option<i32> {
+        TZOLKIN_NAMES.iter().position(|&n| n == self.yucatec_name).map(|i| i as i32)
+    }
+
+    /// Position in the 260-day count (1 Imix = 0, 13 Ajaw = 259), or None for
+    /// a number outside 1-13 or a name not in `TZOLKIN_NAMES`
+    pub fn ordinal(&self) -> Option<i32> {
+        let name_index = self.veintena_position()?;
+        // 40 is 1 mod 13 and 0 mod 20; 221 is 0 mod 13 and 1 mod 20
+        (1..=13)
+            .contains(&self.number)
+            .then(|| (40 * (self.number - 1) + 221 * name_index) % 260)
+    }
+
+    /// The date with its K'iche' day name alongside, e.g. "4 Ajaw (Ajpu)"
+    pub fn with_kiche(&self) -> String {
+        let kiche = TZOLKIN_NAMES
+            .iter()
+            .position(|&n| n == self.yucatec_name)
+            .and_then(|index| day_name(index, NameSet::Kiche));
+        match kiche {
+            Some(kiche) => format!("{} ({})", self, kiche),
+            None => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TzolkinDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.number, self.yucatec_name)
+    }
+}
+
+/// Yucatec Tzolk'in day names, in order
+pub const TZOLKIN_NAMES: [&str; 20] = [
+    "Imix", "Ik'", "Ak'b'al", "K'an", "Chikchan",
+    "Kimi", "Manik'", "Lamat", "Muluk", "Ok",
+    "Chuwen", "Eb'", "B'en", "Ix", "Men",
+    "Kib'", "Kab'an", "Etz'nab'", "Kawak", "Ajaw"
+];
+
+pub fn tzolkin_date(days: i32) -> TzolkinDate {
+    // rem_euclid keeps the positions in range for days before 0.0.0.0.0, and
+    // reducing before adding the offset keeps days near i32::MAX from overflowing
+    let number = (days.rem_euclid(13) + TZOLKIN_NUMBER_OFFSET).rem_euclid(13) + 1;
+    let index = (days.rem_euclid(20) + TZOLKIN_NAME_OFFSET).rem_euclid(20) as usize;
+    TzolkinDate {
+        number,
+        yucatec_name: TZOLKIN_NAMES[index].to_string(),
+    }
+}
+
+/// The Tzolk'in date at `ordinal` in the 260-day count (1 Imix = 0), taken mod 260
+pub fn tzolkin_from_ordinal(ordinal: i32) -> TzolkinDate {
+    let ordinal = ordinal.rem_euclid(260);
+    TzolkinDate::new(ordinal % 13 + 1, TZOLKIN_NAMES[(ordinal % 20) as usize])
+}
+
+/// The 260 days laid out as the classic table: each of the 13 columns runs
+/// through the 20 day names in order, so `table[k][j]` has ordinal `20 * k + j`
+/// and the count reads down one column and on into the next
+pub fn tzolkin_table() -> [[TzolkinDate; 20]; 13] {
+    std::array::from_fn(|k| std::array::from_fn(|j| tzolkin_from_ordinal(20 * k as i32 + j as i32)))
+}
+
+/// Ordinal of 4 Ajaw, the Tzolk'in of 0.0.0.0.0
+pub const TZOLKIN_CREATION_ORDINAL: i32 = 159;
+
+/// Position of `days` in the 260-day count (1 Imix = 0), as `TzolkinDate::ordinal`
+pub fn tzolkin_ordinal_from_days(days: i32) -> i32 {
+    (days.rem_euclid(260) + TZOLKIN_CREATION_ORDINAL) % 260
+}
+
+/// The day count on which the trecena holding `days` began, i.e. its last day numbered 1
+pub fn trecena_start_days(days: i32) -> i32 {
+    days - (days.rem_euclid(13) + TZOLKIN_NUMBER_OFFSET).rem_euclid(13)
+}
+
+/// Day count of the first burner day, 3 Chikchan; the others fall every 65
+/// days after it on 3 Ok, 3 Men and 3 Ajaw
+pub const BURNER_BASE_DAYS: i32 = 25;
+
+/// The Yucatec "burner" cycle: which of the four 65-day burner periods
+/// `days_since_creation` falls in (0 for Chikchan, 1 Ok, 2 Men, 3 Ajaw,
+/// named for the burner day that began it) and the days until the next
+/// burner day
+pub fn burner_cycle(days_since_creation: i32) -> (u8, i32) {
+    let since_base = days_since_creation - BURNER_BASE_DAYS;
+    let period = since_base.div_euclid(65).rem_euclid(4) as u8;
+    (period, 65 - since_base.rem_euclid(65))
+}
+
+/// Position of the creation date (0.0.0.0.0 = 8 Kumk'u) within the 365-day Haab':
+/// Kumk'u is the 18th month, so 17 * 20 + 8 = 348
+pub const HAAB_CREATION_OFFSET: i32 = 348;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HaabDate {
+    pub day: i32,
+    pub yucatec_month: String,
+}
+
+impl HaabDate {
+    pub fn new(day: i32, month: &str) -> Self {
+        Self {
+            day,
+            yucatec_month: month.to_string(),
+        }
+    }
+
+    /// Like `new`, but only for months in `HAAB_MONTHS` and days that exist in them
+    pub fn try_new(day: i32, month: &str) -> Result<Self, DateError> {
+        let month_index = HAAB_MONTHS
+            .iter()
+            .position(|&m| m == month)
+            .ok_or_else(|| DateError::HaabMonth(month.to_string()))?;
+        let max = if month_index == 18 { WAYEB_DAYS - 1 } else { 19 };
+        if !(0..=max).contains(&day) {
+            return Err(DateError::HaabDay { day, month: month.to_string(), max });
+        }
+        Ok(Self::new(day, month))
+    }
+
+    /// Position in the 365-day year (0 Pop = 0, 4 Wayeb' = 364), or None if
+    /// the month is unknown or the day doesn't exist in it
+    pub fn position(&self) -> Option<i32> {
+        let month_index = HAAB_MONTHS.iter().position(|&m| m == self.yucatec_month)? as i32;
+        let month_length = if month_index == 18 { WAYEB_DAYS } else { 20 };
+        (0..month_length).contains(&self.day).then_some(month_index * 20 + self.day)
+    }
+
+    /// `position` under the name `TzolkinDate::ordinal` uses
+    pub fn ordinal(&self) -> Option<i32> {
+        self.position()
+    }
+
+    /// Wayeb' only has days 0-4; the other months run 0-19
+    pub fn is_valid(&self) -> bool {
+        self.position().is_some()
+    }
+}
+
+impl fmt::Display for HaabDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.day, self.yucatec_month)
+    }
+}
+
+/// Length of Wayeb', the short 19th "month" closing the Haab' year
+pub const WAYEB_DAYS: i32 = 5;
+
+/// Yucatec Haab' month names, in order
+pub const HAAB_MONTHS: [&str; 19] = [
+    "Pop", "Wo'", "Sip", "Sotz'", "Sek", "Xul", "Yaxk'in", "Mol",
+    "Ch'en", "Yax", "Sak'", "Keh", "Mak", "K'ank'in", "Muwan", "Pax",
+    "K'ayab", "Kumk'u", "Wayeb'"
+];
+
+pub fn haab_date(days: i32) -> HaabDate {
+    let haab_day = (days.rem_euclid(365) + HAAB_CREATION_OFFSET).rem_euclid(365);
+    let month_index = haab_day / 20;
+    let day = haab_day % 20;
+    
+    HaabDate {
+        day,
+        yucatec_month: HAAB_MONTHS[month_index as usize].to_string(),
+    }
+}
+
+/// How the days of a Haab' month are numbered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HaabNumbering {
+    /// 0-19, the first day being the "seating" of the month (0 Pop), as on
+    /// Classic-period monuments at Palenque, Tikal, Copán and elsewhere
+    #[default]
+    Seating,
+    /// 1-20, as in colonial Yucatec sources such as Landa's Relación and the
+    /// Books of Chilam Balam, where the Classic 0 Pop is 1 Pop
+    OneBased,
+}
+
+impl HaabNumbering {
+    fn offset(self) -> i32 {
+        match self {
+            HaabNumbering::Seating => 0,
+            HaabNumbering::OneBased => 1,
+        }
+    }
+}
+
+/// `haab_date` written in the given numbering. Only use the result for
+/// display: `HaabDate::position` and the Calendar Round functions expect
+/// seating numbering.
+pub fn haab_date_numbered(days: i32, numbering: HaabNumbering) -> HaabDate {
+    let mut haab = haab_date(days);
+    haab.day += numbering.offset();
+    haab
+}
+
+/// Day count of the 0 Pop that began the Haab' year containing `days`
+pub fn haab_year_start(days: i32) -> i32 {
+    days - (days.rem_euclid(365) + HAAB_CREATION_OFFSET).rem_euclid(365)
+}
+
+/// Day count of the first Haab' new year (0 Pop) after `from_days`
+pub fn next_haab_new_year(from_days: i32) -> i32 {
+    haab_year_start(from_days) + 365
+}
+
+/// Gregorian date of the first Haab' new year after `from_days`
+pub fn next_haab_new_year_date(from_days: i32, correlation: i32) -> Result<NaiveDate, DateError> {
+    days_to_gregorian(next_haab_new_year(from_days), correlation)
+}
+
+/// Gregorian dates of the four quarter-stations of the Haab' year containing
+/// `from_days`: its year-bearer day (0 Pop), then every quarter of 365 days
+/// (rounded down, so 91, 182 and 273 days in)
+pub fn haab_year_stations(from_days: i32, correlation: i32) -> Result<[NaiveDate; 4], DateError> {
+    let year_start = next_haab_new_year(from_days) - 365;
+    let station = |quarter: i32| days_to_gregorian(year_start + quarter * 365 / 4, correlation);
+    Ok([station(0)?, station(1)?, station(2)?, station(3)?])
+}
+
+/// Length of the Calendar Round (least common multiple of 260 and 365)
+pub const CALENDAR_ROUND_DAYS: i32 = 18980;
+
+/// Finds the day count (0-18979) within the Calendar Round whose Tzolk'in and
+/// Haab' match the given dates, or None if the pair can never coincide
+fn calendar_round_base(tzolkin: &TzolkinDate, haab: &HaabDate) -> Option<i32> {
+    let haab_position = haab.position()?;
+
+    // Day within the 260-day cycle, counted from 0.0.0.0.0
+    let tzolkin_days = (tzolkin.ordinal()? - TZOLKIN_CREATION_ORDINAL).rem_euclid(260);
+    // Day within the 365-day cycle, counted from 0.0.0.0.0
+    let haab_days = (haab_position - HAAB_CREATION_OFFSET).rem_euclid(365);
+
+    // 260 and 365 share a factor of 5, so only pairs agreeing mod 5 ever meet
+    (0..365 / 5)
+        .map(|k| tzolkin_days + 260 * k)
+        .find(|d| d % 365 == haab_days)
+}
+
+/// A Tzolk'in and Haab' pair, naming a day within the 18980-day Calendar Round
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CalendarRound {
+    pub tzolkin: TzolkinDate,
+    pub haab: HaabDate,
+}
+
+impl CalendarRound {
+    pub fn from_days(days: i32) -> Self {
+        Self {
+            tzolkin: tzolkin_date(days),
+            haab: haab_date(days),
+        }
+    }
+
+    /// Days since the last 4 Ajaw 8 Kumk'u (0-18979), or None if the pair can
+    /// never occur
+    pub fn position(&self) -> Option<i32> {
+        calendar_round_base(&self.tzolkin, &self.haab)
+    }
+
+    /// Only 18980 of the 260 x 365 possible pairs ever fall on the same day
+    pub fn is_valid_combination(&self) -> bool {
+        self.position().is_some()
+    }
+}
+
+/// Every Tzolk'in and Haab' pair that can occur, in Calendar Round order from
+/// 4 Ajaw 8 Kumk'u, so the pair at index `i` has position `i`
+pub fn all_calendar_round_combinations() -> Vec<CalendarRound> {
+    (0..CALENDAR_ROUND_DAYS).map(CalendarRound::from_days).collect()
+}
+
+/// The conventional Calendar Round form of a date, e.g. "4 Ajaw 8 Kumk'u"
+pub fn calendar_round_string(tzolkin: &TzolkinDate, haab: &HaabDate) -> String {
+    format!("{} {}", tzolkin, haab)
+}
+
+/// Returns the first day count after `from_days` falling on the given
+/// Tzolk'in and Haab' combination, or None if the combination is impossible
+pub fn next_calendar_round(from_days: i32, tzolkin: &TzolkinDate, haab: &HaabDate) -> Option<i32> {
+    let base = calendar_round_base(tzolkin, haab)?;
+    let start = from_days + 1;
+    Some(start + (base - start).rem_euclid(CALENDAR_ROUND_DAYS))
+}
+
+/// Returns the last day count before `from_days` falling on the given
+/// Tzolk'in and Haab' combination, or None if the combination is impossible
+pub fn prev_calendar_round(from_days: i32, tzolkin: &TzolkinDate, haab: &HaabDate) -> Option<i32> {
+    let base = calendar_round_base(tzolkin, haab)?;
+    let end = from_days - 1;
+    Some(end - (end - base).rem_euclid(CALENDAR_ROUND_DAYS))
+}
+
+/// Every Gregorian date from `start` to `end` (inclusive) that falls on the
+/// Calendar Round `cr`, about one per 52 years; empty if `cr` can never occur
+pub fn find_calendar_round_dates(cr: &CalendarRound, start: NaiveDate, end: NaiveDate, correlation: i32) -> Vec<NaiveDate> {
+    let Some(base) = cr.position() else {
+        return Vec::new();
+    };
+    let to_days = |date: NaiveDate| gregorian_to_jdn(date.year(), date.month() as i32, date.day() as i32) - correlation;
+    let (first, last) = (to_days(start), to_days(end));
+    let first = first + (base - first).rem_euclid(CALENDAR_ROUND_DAYS);
+    (first..=last)
+        .step_by(CALENDAR_ROUND_DAYS as usize)
+        // Every day between two valid dates has a Gregorian date too
+        .filter_map(|days| days_to_gregorian(days, correlation).ok())
+        .collect()
+}
+
+/// World direction of an 819-day count station
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    East,
+    North,
+    West,
+    South,
+}
+
+/// Color associated with each 819-day count direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+    Red,
+    White,
+    Black,
+    Yellow,
+}
+
+/// Position in the 819-day count and the station's direction and color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Station819 {
+    pub day: i32,
+    pub direction: Direction,
+    pub color: Color,
+}
+
+impl From<(i32, Direction, Color)> for Station819 {
+    fn from((day, direction, color): (i32, Direction, Color)) -> Self {
+        Self { day, direction, color }
+    }
+}
+
+/// Lord of the Night (1-9 for G1-G9) ruling a day; the cycle puts G9 on
+/// 0.0.0.0.0 and so on every period ending
+pub fn lord_of_the_night(days_since_creation: i32) -> i32 {
+    (days_since_creation - 1).rem_euclid(9) + 1
+}
+
+/// The 819-day count base (1 Kab'an 5 Kumk'u) falls three days before 0.0.0.0.0
+pub const COUNT_819_BASE_OFFSET: i32 = 3;
+
+/// Calculates the 819-day count: days elapsed since the last station (0-818)
+/// and the direction/color of the current station, which rotate
+/// counterclockwise east-red, north-white, west-black, south-yellow
+pub fn count_819(days_since_creation: i32) -> (i32, Direction, Color) {
+    let days = days_since_creation + COUNT_819_BASE_OFFSET;
+    let position = days.rem_euclid(819);
+    let station = days.div_euclid(819).rem_euclid(4);
+    match station {
+        0 => (position, Direction::East, Color::Red),
+        1 => (position, Direction::North, Color::White),
+        2 => (position, Direction::West, Color::Black),
+        _ => (position, Direction::South, Color::Yellow),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gregorian_to_jdn_bce() {
+        // 11 August 3114 BCE (astronomical year -3113) is the GMT creation date
+        assert_eq!(gregorian_to_jdn(-3113, 8, 11), 584283);
+        // JDN 0 is 24 November 4714 BCE in the proleptic Gregorian calendar
+        assert_eq!(gregorian_to_jdn(-4713, 11, 24), 0);
+        // Before 4800 BCE the intermediate year goes negative; -4800 is a leap year
+        assert_eq!(gregorian_to_jdn(-4800, 3, 1) - gregorian_to_jdn(-4801, 3, 1), 366);
+        assert_eq!(gregorian_to_jdn(1, 1, 1) - gregorian_to_jdn(0, 1, 1), 366);
+    }
+
+    #[test]
+    fn test_checked_gregorian() {
+        assert_eq!(checked_gregorian(2024, 2, 29), Ok(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()));
+        assert_eq!(checked_gregorian(-3113, 8, 11).map(|d| d.year()), Ok(-3113));
+        for (year, month, day) in [(0, 13, 1), (2023, 2, 29), (2024, 0, 1), (2024, 4, 31), (2024, 1, -1), (i32::MAX, 1, 1)] {
+            assert_eq!(checked_gregorian(year, month, day), Err(DateError::Gregorian { year, month, day }));
+        }
+        assert_eq!(
+            DateError::Gregorian { year: 0, month: 13, day: 1 }.to_string(),
+            "Invalid Gregorian date 0-13-01"
+        );
+    }
+
+    #[test]
+    fn test_julian_to_jdn() {
+        // In 1511 the Julian calendar ran ten days behind the Gregorian
+        assert_eq!(julian_to_jdn(1511, 8, 1), 2273163);
+        assert_eq!(julian_to_jdn(1511, 8, 1) - gregorian_to_jdn(1511, 8, 1), 10);
+        assert_eq!(CalendarSystem::Historical.to_jdn(1511, 8, 1), julian_to_jdn(1511, 8, 1));
+        // The two agree in the 3rd century, and JDN 0 is 1 January 4713 BCE Julian
+        assert_eq!(julian_to_jdn(250, 3, 1), gregorian_to_jdn(250, 3, 1));
+        assert_eq!(julian_to_jdn(-4712, 1, 1), 0);
+        // Thursday 4 October 1582 was followed by Friday 15 October
+        let switch = CalendarSystem::Historical;
+        assert_eq!(switch.to_jdn(1582, 10, 15) - switch.to_jdn(1582, 10, 4), 1);
+        assert_eq!(switch.to_jdn(1697, 3, 13), gregorian_to_jdn(1697, 3, 13));
+        assert_eq!(CalendarSystem::Julian.to_jdn(1697, 3, 13), gregorian_to_jdn(1697, 3, 23));
+    }
+
+    #[test]
+    fn test_calendar_system_parse_date() {
+        let ymd = |y, m, d| Ok(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+        let (gregorian, julian, historical) =
+            (CalendarSystem::Gregorian, CalendarSystem::Julian, CalendarSystem::Historical);
+        assert_eq!(gregorian.parse_date("2012-12-21"), ymd(2012, 12, 21));
+        assert_eq!(gregorian.parse_date("-3113-08-11"), ymd(-3113, 8, 11));
+        assert_eq!(historical.parse_date("1511-08-01"), ymd(1511, 8, 11));
+        assert_eq!(julian.parse_date("1511-08-01"), ymd(1511, 8, 11));
+        assert_eq!(historical.parse_date("1582-10-04"), ymd(1582, 10, 14));
+        assert_eq!(historical.parse_date("1582-10-15"), ymd(1582, 10, 15));
+        assert_eq!(historical.parse_date("1697-03-13"), ymd(1697, 3, 13));
+        assert_eq!(julian.parse_date("1697-03-13"), ymd(1697, 3, 23));
+
+        // 1500 was a leap year in the Julian calendar only
+        assert_eq!(julian.parse_date("1500-02-29"), ymd(1500, 3, 10));
+        assert_eq!(gregorian.parse_date("1500-02-29"), Err(DateError::Gregorian { year: 1500, month: 2, day: 29 }));
+        // The ten days dropped in 1582 never happened
+        assert_eq!(historical.parse_date("1582-10-10"), Err(DateError::Julian { year: 1582, month: 10, day: 10 }));
+        assert!(julian.parse_date("1582-10-10").is_ok());
+        assert!(julian.parse_date("1511-04-31").is_err());
+        assert!(julian.parse_date("1511-13-01").is_err());
+        for bad in ["", "1511-08", "1511-08-01-02", "1511/08/01", "x-08-01"] {
+            assert_eq!(julian.parse_date(bad), Err(DateError::Format(bad.to_string())));
+        }
+    }
+
+    #[test]
+    fn test_today_days() {
+        // Zones either side of the date line can be a day apart, but never more
+        let east = today_days(12, 584283);
+        let west = today_days(-12, 584283);
+        assert!((0..=1).contains(&(east - west)), "{} vs {}", east, west);
+        let utc = Utc::now().date_naive();
+        let utc_days = gregorian_to_jdn(utc.year(), utc.month() as i32, utc.day() as i32) - 584283;
+        assert!((utc_days - 1..=utc_days + 1).contains(&Timezone::Local.today_days(584283)));
+        // Offsets past a day are clamped rather than skipping dates
+        assert!((east..=east + 1).contains(&today_days(100, 584283)));
+    }
+
+    #[test]
+    fn test_tzolkin_creation_date() {
+        // 0.0.0.0.0 is the canonical 4 Ajaw
+        let tzolkin = tzolkin_date(0);
+        assert_eq!(tzolkin.number, 4);
+        assert_eq!(tzolkin.yucatec_name, "Ajaw");
+
+        // The next day advances both the number and the name
+        let next = tzolkin_date(1);
+        assert_eq!(next.number, 5);
+        assert_eq!(next.yucatec_name, "Imix");
+    }
+
+    #[test]
+    fn test_trecena_and_veintena() {
+        // Day 9 closes a trecena on 13 Muluk and day 10 opens the next on 1 Ok
+        assert_eq!(tzolkin_date(9), TzolkinDate::new(13, "Muluk"));
+        assert_eq!(tzolkin_date(10), TzolkinDate::new(1, "Ok"));
+        assert_eq!(tzolkin_date(9).trecena(), 13);
+        assert_eq!(tzolkin_date(10).trecena(), 1);
+        assert_eq!(trecena_start_days(9), -3);
+        assert_eq!(trecena_start_days(10), 10);
+        assert_eq!(trecena_start_days(22), 10);
+        assert_eq!(trecena_start_days(23), 23);
+        assert_eq!(tzolkin_date(trecena_start_days(-100)).trecena(), 1);
+        assert_eq!(tzolkin_date(trecena_start_days(i32::MAX)).trecena(), 1);
+        assert_eq!(haab_date(haab_year_start(i32::MAX)), HaabDate::new(0, "Pop"));
+
+        // The veintena runs on regardless of the trecena
+        assert_eq!(tzolkin_date(9).veintena_position(), Some(8));
+        assert_eq!(tzolkin_date(10).veintena_position(), Some(9));
+        assert_eq!(tzolkin_date(0).veintena_position(), Some(19));
+        assert_eq!(tzolkin_date(1).veintena_position(), Some(0));
+        assert_eq!(TzolkinDate::new(1, "Initial").veintena_position(), None);
+    }
+
+    #[test]
+    fn test_burner_cycle() {
+        // The burner days are 3 Chikchan, 3 Ok, 3 Men and 3 Ajaw, 65 days apart
+        let names = ["Chikchan", "Ok", "Men", "Ajaw"];
+        for k in -4..8 {
+            let day = BURNER_BASE_DAYS + 65 * k;
+            let period = k.rem_euclid(4) as u8;
+            assert_eq!(tzolkin_date(day), TzolkinDate::new(3, names[period as usize]));
+            assert_eq!(burner_cycle(day), (period, 65));
+            assert_eq!(burner_cycle(day + 64), (period, 1));
+            assert_eq!(burner_cycle(day - 1).1, 1);
+        }
+        // 0.0.0.0.0 (4 Ajaw) sits in the Ajaw period, 25 days before 3 Chikchan
+        assert_eq!(burner_cycle(0), (3, 25));
+        assert_eq!(burner_cycle(260), burner_cycle(0));
+    }
+
+    #[test]
+    fn test_tzolkin_repeats_every_260_days() {
+        let start = tzolkin_date(0);
+        for days in 1..260 {
+            let t = tzolkin_date(days);
+            assert!(
+                t.number != start.number || t.yucatec_name != start.yucatec_name,
+                "4 Ajaw recurred early on day {}",
+                days
+            );
+        }
+        for days in [0, 37, 259] {
+            let a = tzolkin_date(days);
+            let b = tzolkin_date(days + 260);
+            assert_eq!(a.number, b.number);
+            assert_eq!(a.yucatec_name, b.yucatec_name);
+        }
+    }
+
+    #[test]
+    fn test_negative_days_stay_in_range() {
+        for days in [-1, -17, -260, -365, -819, -18980, -1_872_000, i32::MIN / 2] {
+            let tzolkin = tzolkin_date(days);
+            let haab = haab_date(days);
+            assert!((1..=13).contains(&tzolkin.number), "{}", days);
+            assert!(haab.is_valid(), "{}", days);
+            assert!((0..819).contains(&count_819(days).0), "{}", days);
+            // Stepping forward a whole cycle lands on the same day
+            assert_eq!(tzolkin_date(days + 260), tzolkin);
+            assert_eq!(haab_date(days + 365), haab);
+        }
+        // The day before creation is 3 Kawak 7 Kumk'u
+        assert_eq!(tzolkin_date(-1), TzolkinDate::new(3, "Kawak"));
+        assert_eq!(haab_date(-1), HaabDate::new(7, "Kumk'u"));
+        assert_eq!(tzolkin_date(-260), TzolkinDate::new(4, "Ajaw"));
+        assert_eq!(haab_date(-18980), HaabDate::new(8, "Kumk'u"));
+        assert_eq!(tzolkin_date(-18980), TzolkinDate::new(4, "Ajaw"));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(tzolkin_date(0).to_string(), "4 Ajaw");
+        assert_eq!(haab_date(0).to_string(), "8 Kumk'u");
+        assert_eq!(tzolkin_date(0).with_kiche(), "4 Ajaw (Ajpu)");
+        assert_eq!(TzolkinDate::new(9, "Q'anil").with_kiche(), "9 Q'anil");
+    }
+
+    #[test]
+    fn test_try_new_validates() {
+        assert_eq!(TzolkinDate::try_new(4, "Ajaw"), Ok(TzolkinDate::new(4, "Ajaw")));
+        assert_eq!(TzolkinDate::try_new(0, "Ajaw"), Err(DateError::TzolkinNumber(0)));
+        assert_eq!(TzolkinDate::try_new(14, "Ajaw"), Err(DateError::TzolkinNumber(14)));
+        assert_eq!(TzolkinDate::try_new(1, "Initial"), Err(DateError::TzolkinName("Initial".to_string())));
+
+        assert_eq!(HaabDate::try_new(8, "Kumk'u"), Ok(HaabDate::new(8, "Kumk'u")));
+        assert_eq!(HaabDate::try_new(4, "Wayeb'"), Ok(HaabDate::new(4, "Wayeb'")));
+        assert_eq!(
+            HaabDate::try_new(5, "Wayeb'"),
+            Err(DateError::HaabDay { day: 5, month: "Wayeb'".to_string(), max: 4 })
+        );
+        assert!(matches!(HaabDate::try_new(20, "Pop"), Err(DateError::HaabDay { max: 19, .. })));
+        assert!(matches!(HaabDate::try_new(-1, "Pop"), Err(DateError::HaabDay { .. })));
+        assert_eq!(HaabDate::try_new(1, "Initial"), Err(DateError::HaabMonth("Initial".to_string())));
+
+        // Anything try_new accepts is a real date
+        for days in 0..365 {
+            let haab = haab_date(days);
+            assert_eq!(HaabDate::try_new(haab.day, &haab.yucatec_month), Ok(haab));
+        }
+    }
+
+    #[test]
+    fn test_haab_creation_date() {
+        // 0.0.0.0.0 is the canonical 8 Kumk'u
+        let haab = haab_date(0);
+        assert_eq!(haab.day, 8);
+        assert_eq!(haab.yucatec_month, "Kumk'u");
+
+        // One Haab' year later the same position comes round again
+        let next_year = haab_date(365);
+        assert_eq!(next_year.day, 8);
+        assert_eq!(next_year.yucatec_month, "Kumk'u");
+
+        // 17 days after creation the Haab' year rolls over into Pop
+        let new_year = haab_date(365 - HAAB_CREATION_OFFSET);
+        assert_eq!(new_year.day, 0);
+        assert_eq!(new_year.yucatec_month, "Pop");
+    }
+
+    #[test]
+    fn test_next_haab_new_year() {
+        // 17 days after creation the first Haab' year turns over
+        assert_eq!(next_haab_new_year(0), 17);
+        assert_eq!(next_haab_new_year(-1), 17);
+        assert_eq!(next_haab_new_year(16), 17);
+        assert_eq!(next_haab_new_year(17), 17 + 365);
+
+        let mut days = 1_872_000;
+        for _ in 0..10 {
+            let next = next_haab_new_year(days);
+            assert!(next > days && next - days <= 365);
+            assert_eq!(haab_date(next), HaabDate::new(0, "Pop"));
+            assert_eq!(next_haab_new_year(next) - next, 365);
+            days = next;
+        }
+
+        // The year after 13.0.0.0.0 began on 0 Pop, 2 April 2013
+        assert_eq!(
+            next_haab_new_year_date(1_872_000, 584283),
+            Ok(NaiveDate::from_ymd_opt(2013, 4, 2).unwrap())
+        );
+        assert!(next_haab_new_year_date(i32::MAX - 365, 584283).is_err());
+    }
+
+    #[test]
+    fn test_haab_year_stations() {
+        let stations = haab_year_stations(1_872_000, 584283).unwrap();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(stations, [date(2012, 4, 2), date(2012, 7, 2), date(2012, 10, 1), date(2012, 12, 31)]);
+        for pair in stations.windows(2) {
+            assert_eq!((pair[1] - pair[0]).num_days(), 91);
+        }
+        // All within the one Haab' year, which the next new year closes
+        let today = date(2012, 12, 21);
+        let next_new_year = next_haab_new_year_date(1_872_000, 584283).unwrap();
+        assert!(stations[0] <= today && today < next_new_year);
+        assert!((next_new_year - stations[3]).num_days() <= 92);
+        assert_eq!(haab_year_stations(next_haab_new_year(1_872_000) - 1, 584283), Ok(stations));
+        assert!(haab_year_stations(1_872_000, i32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_haab_numbering() {
+        assert_eq!(haab_date_numbered(0, HaabNumbering::Seating), HaabDate::new(8, "Kumk'u"));
+        assert_eq!(haab_date_numbered(0, HaabNumbering::OneBased), HaabDate::new(9, "Kumk'u"));
+        // Haab' new year: the seating of Pop, or 1 Pop
+        let new_year = next_haab_new_year(0);
+        assert_eq!(haab_date_numbered(new_year, HaabNumbering::Seating), HaabDate::new(0, "Pop"));
+        assert_eq!(haab_date_numbered(new_year, HaabNumbering::OneBased), HaabDate::new(1, "Pop"));
+        // Last days of Pop and of Wayeb'
+        assert_eq!(haab_date_numbered(new_year + 19, HaabNumbering::OneBased), HaabDate::new(20, "Pop"));
+        assert_eq!(haab_date_numbered(new_year - 1, HaabNumbering::Seating), HaabDate::new(4, "Wayeb'"));
+        assert_eq!(haab_date_numbered(new_year - 1, HaabNumbering::OneBased), HaabDate::new(5, "Wayeb'"));
+    }
+
+    #[test]
+    fn test_haab_wayeb() {
+        // Positions 360-364 of the Haab' year are Wayeb' 0-4
+        for position in 360..365 {
+            let haab = haab_date(position - HAAB_CREATION_OFFSET);
+            assert_eq!(haab.yucatec_month, "Wayeb'");
+            assert_eq!(haab.day, position - 360);
+            assert!(haab.is_valid());
+            assert_eq!(haab.position(), Some(position));
+        }
+        // and position 365 wraps round to 0 Pop
+        assert_eq!(haab_date(365 - HAAB_CREATION_OFFSET), HaabDate::new(0, "Pop"));
+
+        assert!(!HaabDate::new(5, "Wayeb'").is_valid());
+        assert!(!HaabDate::new(19, "Wayeb'").is_valid());
+        assert!(HaabDate::new(19, "Kumk'u").is_valid());
+        assert!(!HaabDate::new(20, "Pop").is_valid());
+        assert!(!HaabDate::new(-1, "Pop").is_valid());
+        assert!(!HaabDate::new(0, "Popp").is_valid());
+    }
+
+    #[test]
+    fn test_lord_of_the_night() {
+        assert_eq!(lord_of_the_night(0), 9);
+        assert_eq!(lord_of_the_night(1), 1);
+        assert_eq!(lord_of_the_night(-1), 8);
+        // 13.0.0.0.0 is a period ending, so it is ruled by G9
+        assert_eq!(lord_of_the_night(1872000), 9);
+        assert_eq!(lord_of_the_night(1872000 + 5), 5);
+    }
+
+    #[test]
+    fn test_count_819_base_station() {
+        // The base station 1 Kab'an 5 Kumk'u, from which every recorded count
+        // (such as those in the Palenque Cross Group) is reckoned
+        let base = -COUNT_819_BASE_OFFSET;
+        assert_eq!(count_819(base), (0, Direction::East, Color::Red));
+        assert_eq!(tzolkin_date(base).number, 1);
+        assert_eq!(tzolkin_date(base).yucatec_name, "Kab'an");
+        assert_eq!(haab_date(base).day, 5);
+        assert_eq!(haab_date(base).yucatec_month, "Kumk'u");
+
+        // Creation day is three days into the first station
+        assert_eq!(count_819(0), (3, Direction::East, Color::Red));
+
+        // Stations advance every 819 days and always fall on a Tzolk'in 1
+        assert_eq!(count_819(base + 819), (0, Direction::North, Color::White));
+        assert_eq!(count_819(base + 2 * 819), (0, Direction::West, Color::Black));
+        assert_eq!(count_819(base + 3 * 819), (0, Direction::South, Color::Yellow));
+        assert_eq!(count_819(base + 4 * 819), (0, Direction::East, Color::Red));
+        assert_eq!(count_819(base - 819), (0, Direction::South, Color::Yellow));
+        assert_eq!(tzolkin_date(base + 819 * 7).number, 1);
+    }
+
+    #[test]
+    fn test_calendar_round_string() {
+        assert_eq!(calendar_round_string(&tzolkin_date(0), &haab_date(0)), "4 Ajaw 8 Kumk'u");
+        // 13.0.0.0.0, 21 December 2012
+        let days = 13 * 144_000;
+        assert_eq!(calendar_round_string(&tzolkin_date(days), &haab_date(days)), "4 Ajaw 3 K'ank'in");
+    }
+
+    #[test]
+    fn test_next_calendar_round() {
+        // 4 Ajaw 8 Kumk'u recurs one full Calendar Round after creation
+        let tzolkin = TzolkinDate::new(4, "Ajaw");
+        let haab = HaabDate::new(8, "Kumk'u");
+        assert_eq!(next_calendar_round(0, &tzolkin, &haab), Some(CALENDAR_ROUND_DAYS));
+        assert_eq!(next_calendar_round(-1, &tzolkin, &haab), Some(0));
+        assert_eq!(next_calendar_round(-CALENDAR_ROUND_DAYS, &tzolkin, &haab), Some(0));
+
+        // Any day's own combination comes round again 18980 days later
+        for days in [1, 259, 1_000, 1_872_000] {
+            let t = tzolkin_date(days);
+            let h = haab_date(days);
+            assert_eq!(next_calendar_round(days, &t, &h), Some(days + CALENDAR_ROUND_DAYS));
+            assert_eq!(next_calendar_round(days - 7, &t, &h), Some(days));
+        }
+    }
+
+    #[test]
+    fn test_next_calendar_round_impossible() {
+        // Ajaw only ever falls on Haab' days 3, 8, 13 and 18
+        let ajaw = TzolkinDate::new(4, "Ajaw");
+        assert_eq!(next_calendar_round(0, &ajaw, &HaabDate::new(0, "Pop")), None);
+        assert!(next_calendar_round(0, &ajaw, &HaabDate::new(3, "Pop")).is_some());
+
+        // Out-of-range values and unknown names never match
+        assert_eq!(next_calendar_round(0, &TzolkinDate::new(14, "Ajaw"), &HaabDate::new(8, "Kumk'u")), None);
+        assert_eq!(next_calendar_round(0, &ajaw, &HaabDate::new(8, "Wayeb'")), None);
+        assert_eq!(next_calendar_round(0, &TzolkinDate::new(4, "Initial"), &HaabDate::new(8, "Kumk'u")), None);
+    }
+
+    #[test]
+    fn test_ordinals() {
+        assert_eq!(TzolkinDate::new(1, "Imix").ordinal(), Some(0));
+        assert_eq!(TzolkinDate::new(13, "Ajaw").ordinal(), Some(259));
+        assert_eq!(TzolkinDate::new(4, "Ajaw").ordinal(), Some(TZOLKIN_CREATION_ORDINAL));
+        assert_eq!(TzolkinDate::new(14, "Ajaw").ordinal(), None);
+        assert_eq!(TzolkinDate::new(1, "Initial").ordinal(), None);
+        assert_eq!(HaabDate::new(0, "Pop").ordinal(), Some(0));
+        assert_eq!(HaabDate::new(4, "Wayeb'").ordinal(), Some(364));
+        assert_eq!(HaabDate::new(5, "Wayeb'").ordinal(), None);
+        assert_eq!(HaabDate::new(0, "Initial").ordinal(), None);
+
+        // Ordinals advance one a day from whatever day count they start on
+        for days in (-1_000..1_000).chain([1_872_000, i32::MIN, i32::MAX]) {
+            let ordinal = tzolkin_ordinal_from_days(days);
+            assert_eq!(tzolkin_date(days).ordinal(), Some(ordinal));
+            assert_eq!(haab_date(days).ordinal(), Some((days.rem_euclid(365) + HAAB_CREATION_OFFSET) % 365));
+            if days < i32::MAX {
+                assert_eq!(tzolkin_ordinal_from_days(days + 1), (ordinal + 1) % 260);
+            }
+        }
+        assert_eq!(tzolkin_ordinal_from_days(0), 159);
+        assert_eq!(haab_date(0).ordinal(), Some(HAAB_CREATION_OFFSET));
+    }
+
+    #[test]
+    fn test_tzolkin_table() {
+        let table = tzolkin_table();
+        assert_eq!(table[0][0], TzolkinDate::new(1, "Imix"));
+        assert_eq!(table[0][13], TzolkinDate::new(1, "Ix"));
+        assert_eq!(table[1][0], TzolkinDate::new(8, "Imix"));
+        assert_eq!(table[12][19], TzolkinDate::new(13, "Ajaw"));
+        let mut seen = std::collections::HashSet::new();
+        for (k, column) in table.iter().enumerate() {
+            for (j, day) in column.iter().enumerate() {
+                assert_eq!(day.ordinal(), Some(20 * k as i32 + j as i32));
+                assert_eq!(day.veintena_position(), Some(j as i32));
+                assert!(seen.insert(day.clone()));
+            }
+        }
+        assert_eq!(tzolkin_from_ordinal(-1), TzolkinDate::new(13, "Ajaw"));
+    }
+
+    #[test]
+    fn test_all_calendar_round_combinations() {
+        let combinations = all_calendar_round_combinations();
+        assert_eq!(combinations.len(), 18980);
+        assert_eq!(calendar_round_string(&combinations[0].tzolkin, &combinations[0].haab), "4 Ajaw 8 Kumk'u");
+        let distinct: std::collections::HashSet<_> =
+            combinations.iter().map(|cr| (cr.tzolkin.clone(), cr.haab.clone())).collect();
+        assert_eq!(distinct.len(), 18980);
+        for (i, cr) in combinations.iter().enumerate() {
+            let days = i as i32;
+            assert_eq!(cr.position(), Some(days));
+            // The sequence repeats exactly, forward and back
+            assert_eq!(&CalendarRound::from_days(days + CALENDAR_ROUND_DAYS), cr);
+            assert_eq!(&CalendarRound::from_days(days - 7 * CALENDAR_ROUND_DAYS), cr);
+        }
+    }
+
+    #[test]
+    fn test_prev_calendar_round() {
+        let tzolkin = TzolkinDate::new(4, "Ajaw");
+        let haab = HaabDate::new(8, "Kumk'u");
+        assert_eq!(prev_calendar_round(0, &tzolkin, &haab), Some(-CALENDAR_ROUND_DAYS));
+        assert_eq!(prev_calendar_round(1, &tzolkin, &haab), Some(0));
+        assert_eq!(prev_calendar_round(CALENDAR_ROUND_DAYS, &tzolkin, &haab), Some(0));
+        assert_eq!(prev_calendar_round(0, &tzolkin, &HaabDate::new(0, "Pop")), None);
+
+        // Forward then back lands on the day we started from
+        for days in [-1_000_000, -1, 0, 259, 1_872_000] {
+            let t = tzolkin_date(days);
+            let h = haab_date(days);
+            let next = next_calendar_round(days, &t, &h).unwrap();
+            assert_eq!(prev_calendar_round(next, &t, &h), Some(days));
+            assert_eq!(prev_calendar_round(days + 7, &t, &h), Some(days));
+        }
+    }
+
+    #[test]
+    fn test_find_calendar_round_dates() {
+        // 4 Ajaw 3 K'ank'in, the Calendar Round of 13.0.0.0.0
+        let cr = CalendarRound::from_days(13 * 144_000);
+        let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let dates = find_calendar_round_dates(&cr, ymd(1900, 1, 1), ymd(2099, 12, 31), 584283);
+        assert_eq!(dates.len(), 4);
+        assert!(dates.contains(&ymd(2012, 12, 21)));
+        for pair in dates.windows(2) {
+            assert_eq!((pair[1] - pair[0]).num_days(), CALENDAR_ROUND_DAYS as i64);
+        }
+        for date in &dates {
+            let days = gregorian_to_jdn(date.year(), date.month() as i32, date.day() as i32) - 584283;
+            assert_eq!(CalendarRound::from_days(days), cr);
+        }
+
+        // Both ends are inclusive, and an impossible pair finds nothing
+        assert_eq!(find_calendar_round_dates(&cr, ymd(2012, 12, 21), ymd(2012, 12, 21), 584283), [ymd(2012, 12, 21)]);
+        assert!(find_calendar_round_dates(&cr, ymd(2012, 12, 22), ymd(2060, 1, 1), 584283).is_empty());
+        let impossible = CalendarRound { tzolkin: TzolkinDate::new(4, "Ajaw"), haab: HaabDate::new(0, "Pop") };
+        assert!(find_calendar_round_dates(&impossible, ymd(1900, 1, 1), ymd(2099, 12, 31), 584283).is_empty());
+    }
+
+    #[test]
+    fn test_calendar_round_repeats_every_18980_days() {
+        // Every pair in the cycle is distinct and recurs exactly one round away, either side
+        let mut seen = std::collections::HashSet::new();
+        for days in 0..CALENDAR_ROUND_DAYS {
+            let (t, h) = (tzolkin_date(days), haab_date(days));
+            assert_eq!(tzolkin_date(days + CALENDAR_ROUND_DAYS), t);
+            assert_eq!(haab_date(days + CALENDAR_ROUND_DAYS), h);
+            assert_eq!(tzolkin_date(days - CALENDAR_ROUND_DAYS), t);
+            assert_eq!(haab_date(days - CALENDAR_ROUND_DAYS), h);
+            assert!(seen.insert((t, h)), "pair repeated on day {}", days);
+        }
+    }
+
+    #[test]
+    fn test_calendar_round_cycle() {
+        // One full cycle visits 18980 distinct pairs, each at its own position
+        let mut seen = std::collections::HashSet::new();
+        for days in 0..CALENDAR_ROUND_DAYS {
+            let round = CalendarRound::from_days(days);
+            assert_eq!(round.position(), Some(days));
+            assert!(seen.insert(calendar_round_string(&round.tzolkin, &round.haab)));
+        }
+        assert_eq!(CalendarRound::from_days(CALENDAR_ROUND_DAYS).position(), Some(0));
+
+        // And they are the only valid pairs out of all 260 x 365
+        let valid = (0..260)
+            .flat_map(|t| (0..365).map(move |h| (t, h)))
+            .filter(|&(t, h)| {
+                CalendarRound { tzolkin: tzolkin_date(t), haab: haab_date(h) }.is_valid_combination()
+            })
+            .count();
+        assert_eq!(valid, CALENDAR_ROUND_DAYS as usize);
+    }
+
+    #[test]
+    fn test_cycle_positions_at_i32_limits() {
+        // Random inputs rarely land this close to the edge
+        for days in [i32::MIN, i32::MIN + 1, i32::MAX - 19, i32::MAX] {
+            assert!((1..=13).contains(&tzolkin_date(days).number));
+            assert!(haab_date(days).day < 20);
+        }
+        assert_eq!(tzolkin_date(i32::MAX), tzolkin_date(i32::MAX - 260));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_cycle_positions_in_range(days in proptest::num::i32::ANY) {
+            let tzolkin = tzolkin_date(days);
+            proptest::prop_assert!((1..=13).contains(&tzolkin.number));
+            proptest::prop_assert!(tzolkin.is_valid());
+            let haab = haab_date(days);
+            let month = HAAB_MONTHS.iter().position(|&m| m == haab.yucatec_month);
+            proptest::prop_assert!(month.is_some());
+            let max_day = if month == Some(18) { 4 } else { 19 };
+            proptest::prop_assert!((0..=max_day).contains(&haab.day));
+        }
+
+        #[test]
+        fn prop_cycle_starts_in_range(days in (i32::MIN + 364)..=i32::MAX) {
+            // Counts this close to i32::MIN may have begun before it
+            let trecena_start = trecena_start_days(days);
+            proptest::prop_assert!((0..13).contains(&(days - trecena_start)));
+            proptest::prop_assert_eq!(tzolkin_date(trecena_start).number, 1);
+            let year_start = haab_year_start(days);
+            proptest::prop_assert!((0..365).contains(&(days - year_start)));
+            proptest::prop_assert_eq!(haab_date(year_start), HaabDate::new(0, "Pop"));
+            if let Some(from_days) = days.checked_sub(365) {
+                proptest::prop_assert!(next_haab_new_year(from_days) > from_days);
+            }
+        }
+
+        #[test]
+        fn prop_cycles_match_neighbouring_days(days in -100_000_000..100_000_000i32) {
+            // One day on, both counts advance by exactly one position
+            let (today, tomorrow) = (tzolkin_date(days), tzolkin_date(days + 1));
+            proptest::prop_assert_eq!(tomorrow.number, today.number % 13 + 1);
+            proptest::prop_assert_eq!(
+                tomorrow.veintena_position().unwrap(),
+                (today.veintena_position().unwrap() + 1) % 20
+            );
+            proptest::prop_assert_eq!(tzolkin_date(days + 260), today);
+            proptest::prop_assert_eq!(haab_date(days + 365), haab_date(days));
+        }
+    }
+}
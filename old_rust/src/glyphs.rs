@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use crate::date_utils::{HAAB_MONTHS, TZOLKIN_NAMES};
+
+/// Convert a number (0-19) to a Mayan numeral Unicode character
+pub fn mayan_numeral(n: i32) -> char {
+    match n {
+        0..=19 => char::from_u32(0x1D2E0 + n as u32).unwrap(),
+        _ => '❓', // If out of range, return a placeholder
+    }
+}
+
+/// Draws a single Maya digit (0-19) as ASCII art, one line per row: the dots
+/// on top and the bars beneath, as on the monuments. Zero is the shell glyph
+/// and anything out of range is a placeholder.
+pub fn mayan_ascii_number(n: i32) -> String {
+    match n {
+        0 => "𝋠\n".to_string(),
+        1..=19 => {
+            let mut result = String::new();
+            let dots = (n % 5) as usize;
+            if dots > 0 {
+                result.push_str(&"●".repeat(dots));
+                result.push('\n');
+            }
+            for _ in 0..n / 5 {
+                result.push_str("▬▬▬▬▬▬\n");
+            }
+            result
+        }
+        _ => "❓\n".to_string(),
+    }
+}
+
+/// Renders `n` as a positional base-20 Maya numeral, one bar-and-dot stack
+/// per position from the highest down; empty positions get the shell glyph.
+/// Maya numerals have no sign, so a negative `n` is shown by its magnitude.
+pub fn mayan_numeral_vigesimal(n: i64) -> Vec<String> {
+    let mut rest = n.unsigned_abs();
+    let mut positions = vec![mayan_ascii_number((rest % 20) as i32)];
+    rest /= 20;
+    while rest > 0 {
+        positions.push(mayan_ascii_number((rest % 20) as i32));
+        rest /= 20;
+    }
+    positions.reverse();
+    positions
+}
+
+/// Get Tzolk'in Day Glyphs
+pub fn tzolkin_glyphs() -> HashMap<&'static str, &'static str> {
+    let mut glyphs = HashMap::new();
+    let tzolkin_symbols = [
+        "🐊", "🌬️", "🌑", "🌽", "🐍",
+        "💀", "🖐️", "🌟", "💧", "🐶",
+        "🕷️", "🌾", "🌳", "🦉", "🦅",
+        "🐝", "🌀", "🔪", "⛈️", "👑"
+    ];
+    for i in 0..20 {
+        glyphs.insert(TZOLKIN_NAMES[i], tzolkin_symbols[i]);
+    }
+    glyphs
+}
+
+/// Get Haab' Month Glyphs
+pub fn haab_glyphs() -> HashMap<&'static str, &'static str> {
+    let mut glyphs = HashMap::new();
+    let haab_symbols = [
+        "📜", "🌊", "🔥", "🦇", "🌱", "💨", "🌞", "🌧️",
+        "🏺", "🌿", "❄️", "🐆", "🎭", "🔥", "🦜", "🎵",
+        "🐢", "🌰", "⚠️"
+    ];
+    for i in 0..19 {
+        glyphs.insert(HAAB_MONTHS[i], haab_symbols[i]);
+    }
+    glyphs
+}
+
+/// Get Long Count Glyphs
+pub fn long_count_glyphs() -> HashMap<i32, &'static str> {
+    let mut glyphs = HashMap::new();
+    glyphs.insert(0, "🪵");  // Placeholder for Baktun glyph
+    glyphs.insert(1, "🔥");
+    glyphs.insert(2, "💧");
+    glyphs.insert(3, "🌿");
+    glyphs.insert(4, "🌞");
+    glyphs.insert(5, "🌕");
+    glyphs.insert(6, "🌎");
+    glyphs.insert(7, "🐍");
+    glyphs.insert(8, "🌪️");
+    glyphs.insert(9, "⭐");
+    glyphs.insert(10, "🔺");
+    glyphs.insert(11, "🏹");
+    glyphs.insert(12, "🌀");
+    glyphs.insert(13, "🔮");
+    glyphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mayan_ascii_number() {
+        assert_eq!(mayan_ascii_number(0), "𝋠\n");
+        assert_eq!(mayan_ascii_number(3), "●●●\n");
+        assert_eq!(mayan_ascii_number(5), "▬▬▬▬▬▬\n");
+        assert_eq!(mayan_ascii_number(13), "●●●\n▬▬▬▬▬▬\n▬▬▬▬▬▬\n");
+        assert_eq!(mayan_ascii_number(19), "●●●●\n▬▬▬▬▬▬\n▬▬▬▬▬▬\n▬▬▬▬▬▬\n");
+        assert_eq!(mayan_ascii_number(20), "❓\n");
+        assert_eq!(mayan_ascii_number(-1), "❓\n");
+    }
+
+    #[test]
+    fn test_mayan_numeral_vigesimal() {
+        assert_eq!(mayan_numeral_vigesimal(0), ["𝋠\n"]);
+        assert_eq!(mayan_numeral_vigesimal(19), [mayan_ascii_number(19)]);
+        assert_eq!(mayan_numeral_vigesimal(20), [mayan_ascii_number(1), mayan_ascii_number(0)]);
+        assert_eq!(
+            mayan_numeral_vigesimal(400),
+            [mayan_ascii_number(1), mayan_ascii_number(0), mayan_ascii_number(0)]
+        );
+        // 420 = 1 x 400 + 1 x 20 + 0
+        assert_eq!(
+            mayan_numeral_vigesimal(420),
+            [mayan_ascii_number(1), mayan_ascii_number(1), mayan_ascii_number(0)]
+        );
+        assert_eq!(mayan_numeral_vigesimal(-20), mayan_numeral_vigesimal(20));
+        assert_eq!(mayan_numeral_vigesimal(i64::MIN).len(), 15);
+    }
+}
@@ -0,0 +1,1449 @@
+use std::sync::Arc;
+use std::sync::{Mutex, RwLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+use ab_glyph::{Font, FontArc, ScaleFont};
+use chrono::{Datelike, NaiveDate, Timelike};
+use eframe::App;
+use egui::{self, Context, Key, TextureHandle, ColorImage, TextureOptions, Vec2};
+use image::{DynamicImage, Rgba, RgbaImage};
+
+use mayan_calendar::calendar::{CalendarCache, CalendarData, ParallelCalendarCalculator};
+use mayan_calendar::config::Config;
+use mayan_calendar::correlation::{compare_correlations, jdn_to_gregorian, NAMED_CORRELATIONS};
+use mayan_calendar::date_utils::{
+    calendar_round_string, gregorian_to_jdn, haab_date_numbered, lord_of_the_night, tzolkin_table, HaabNumbering, Timezone, TzolkinDate,
+    HAAB_MONTHS, TZOLKIN_NAMES,
+};
+use mayan_calendar::glyphs::{haab_glyphs, mayan_numeral, tzolkin_glyphs};
+use mayan_calendar::long_count::{days_between, distance, LongCount};
+use mayan_calendar::metrics::{Instant, Metrics};
+use mayan_calendar::names::{day_name, month_name, tzolkin_meaning, NameSet};
+
+// Enum for Glyph Types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GlyphType {
+    Tzolkin,
+    Haab,
+    /// Lords of the Night, G1-G9
+    Night,
+}
+
+// Texture Cache
+// Keyed by glyph name, so a frame finds its textures without going through
+// the configured file paths
+#[derive(Default)]
+pub struct TextureCache {
+    textures: HashMap<(GlyphType, String), TextureHandle>,
+    // Glyphs whose files couldn't be loaded; the text fallback is drawn
+    // instead of going back to disk every frame
+    failed: HashSet<(GlyphType, String)>,
+}
+
+impl TextureCache {
+    fn get(&self, glyph_type: GlyphType, name: &str) -> Option<&TextureHandle> {
+        self.textures.get(&(glyph_type, name.to_string()))
+    }
+
+    fn contains(&self, glyph_type: GlyphType, name: &str) -> bool {
+        self.get(glyph_type, name).is_some()
+    }
+
+    fn insert(&mut self, glyph_type: GlyphType, name: String, texture: TextureHandle) {
+        self.textures.insert((glyph_type, name), texture);
+    }
+
+    fn mark_failed(&mut self, glyph_type: GlyphType, name: String) {
+        self.failed.insert((glyph_type, name));
+    }
+
+    fn has_failed(&self, glyph_type: GlyphType, name: &str) -> bool {
+        self.failed.contains(&(glyph_type, name.to_string()))
+    }
+}
+
+// Glyph Error Handling
+#[derive(Debug, thiserror::Error)]
+pub enum GlyphError {
+    #[error("Failed to open file: {0}")]
+    FileError(std::io::Error),
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("Memory mapping failed: {0}")]
+    MmapError(std::io::Error),
+    #[cfg(target_arch = "wasm32")]
+    #[error("Glyph is not bundled: {0}")]
+    NotBundled(PathBuf),
+    #[error("Failed to load image: {0}")]
+    ImageLoadError(#[from] image::ImageError),
+    #[error("Invalid glyph dimensions: {0}x{1}")]
+    InvalidDimensions(u32, u32),
+    #[error("Invalid atlas manifest: {0}")]
+    ManifestError(#[from] serde_json::Error),
+    #[error("Atlas rect for {0} lies outside the {1}x{2} atlas")]
+    AtlasBounds(String, u32, u32),
+    #[cfg(feature = "svg")]
+    #[error("Failed to parse SVG: {0}")]
+    SvgError(#[from] resvg::usvg::Error),
+}
+
+impl GlyphError {
+    /// Whether only a single glyph is lost, so the calendar carries on with a
+    /// text fallback. A bad atlas manifest or rect loses the whole atlas.
+    pub fn is_recoverable(&self) -> bool {
+        !matches!(self, GlyphError::ManifestError(_) | GlyphError::AtlasBounds(..))
+    }
+}
+
+/// Edge length SVG glyphs are rasterized to; twice the display size so they stay crisp when zoomed
+#[cfg(feature = "svg")]
+const SVG_RASTER_SIZE: u32 = 256;
+
+// Render an SVG so its longer side is `size` pixels
+#[cfg(feature = "svg")]
+fn rasterize_svg(data: &[u8], size: u32) -> Result<image::DynamicImage, GlyphError> {
+    let tree = resvg::usvg::Tree::from_data(data, &resvg::usvg::Options::default())?;
+    let scale = size as f32 / tree.size().width().max(tree.size().height());
+    let width = (tree.size().width() * scale).round() as u32;
+    let height = (tree.size().height() * scale).round() as u32;
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height)
+        .ok_or(GlyphError::InvalidDimensions(width, height))?;
+    resvg::render(&tree, resvg::tiny_skia::Transform::from_scale(scale, scale), &mut pixmap.as_mut());
+    // tiny-skia works in premultiplied alpha, the image crate in straight alpha
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|p| {
+            let c = p.demultiply();
+            [c.red(), c.green(), c.blue(), c.alpha()]
+        })
+        .collect();
+    let buffer = image::RgbaImage::from_raw(width, height, pixels)
+        .ok_or(GlyphError::InvalidDimensions(width, height))?;
+    Ok(image::DynamicImage::ImageRgba8(buffer))
+}
+
+// Memory-map a glyph file from the asset directory
+#[cfg(not(target_arch = "wasm32"))]
+fn read_glyph(path: &Path) -> Result<memmap2::Mmap, GlyphError> {
+    let file = std::fs::File::open(path).map_err(GlyphError::FileError)?;
+    // SAFETY: glyph assets are read-only and not modified while the app runs
+    unsafe { memmap2::Mmap::map(&file) }.map_err(GlyphError::MmapError)
+}
+
+// Glyphs compiled into the binary, since the browser has no asset directory
+#[cfg(target_arch = "wasm32")]
+static BUNDLED_GLYPHS: &[(&str, &[u8])] = &[
+    ("tzolkin/glyphs/ajaw.png", include_bytes!("../assets/tzolkin/glyphs/ajaw.png")),
+    ("tzolkin/glyphs/akbal.png", include_bytes!("../assets/tzolkin/glyphs/akbal.png")),
+    ("tzolkin/glyphs/ben.png", include_bytes!("../assets/tzolkin/glyphs/ben.png")),
+    ("tzolkin/glyphs/chikchan.png", include_bytes!("../assets/tzolkin/glyphs/chikchan.png")),
+    ("tzolkin/glyphs/chuwen.png", include_bytes!("../assets/tzolkin/glyphs/chuwen.png")),
+    ("tzolkin/glyphs/eb.png", include_bytes!("../assets/tzolkin/glyphs/eb.png")),
+    ("tzolkin/glyphs/etznab.png", include_bytes!("../assets/tzolkin/glyphs/etznab.png")),
+    ("tzolkin/glyphs/ik.png", include_bytes!("../assets/tzolkin/glyphs/ik.png")),
+    ("tzolkin/glyphs/imix.png", include_bytes!("../assets/tzolkin/glyphs/imix.png")),
+    ("tzolkin/glyphs/ix.png", include_bytes!("../assets/tzolkin/glyphs/ix.png")),
+    ("tzolkin/glyphs/kaban.png", include_bytes!("../assets/tzolkin/glyphs/kaban.png")),
+    ("tzolkin/glyphs/kan.png", include_bytes!("../assets/tzolkin/glyphs/kan.png")),
+    ("tzolkin/glyphs/kawak.png", include_bytes!("../assets/tzolkin/glyphs/kawak.png")),
+    ("tzolkin/glyphs/kib.png", include_bytes!("../assets/tzolkin/glyphs/kib.png")),
+    ("tzolkin/glyphs/kimi.png", include_bytes!("../assets/tzolkin/glyphs/kimi.png")),
+    ("tzolkin/glyphs/lamat.png", include_bytes!("../assets/tzolkin/glyphs/lamat.png")),
+    ("tzolkin/glyphs/manik.png", include_bytes!("../assets/tzolkin/glyphs/manik.png")),
+    ("tzolkin/glyphs/men.png", include_bytes!("../assets/tzolkin/glyphs/men.png")),
+    ("tzolkin/glyphs/muluk.png", include_bytes!("../assets/tzolkin/glyphs/muluk.png")),
+    ("tzolkin/glyphs/ok.png", include_bytes!("../assets/tzolkin/glyphs/ok.png")),
+    ("haab/glyphs/chen.png", include_bytes!("../assets/haab/glyphs/chen.png")),
+    ("haab/glyphs/kanxin.png", include_bytes!("../assets/haab/glyphs/kanxin.png")),
+    ("haab/glyphs/kayab.png", include_bytes!("../assets/haab/glyphs/kayab.png")),
+    ("haab/glyphs/koh.png", include_bytes!("../assets/haab/glyphs/koh.png")),
+    ("haab/glyphs/kunxu.png", include_bytes!("../assets/haab/glyphs/kunxu.png")),
+    ("haab/glyphs/mal.png", include_bytes!("../assets/haab/glyphs/mal.png")),
+    ("haab/glyphs/max.png", include_bytes!("../assets/haab/glyphs/max.png")),
+    ("haab/glyphs/muwan.png", include_bytes!("../assets/haab/glyphs/muwan.png")),
+    ("haab/glyphs/pax.png", include_bytes!("../assets/haab/glyphs/pax.png")),
+    ("haab/glyphs/pop.png", include_bytes!("../assets/haab/glyphs/pop.png")),
+    ("haab/glyphs/sax.png", include_bytes!("../assets/haab/glyphs/sax.png")),
+    ("haab/glyphs/siq.png", include_bytes!("../assets/haab/glyphs/siq.png")),
+    ("haab/glyphs/sotj.png", include_bytes!("../assets/haab/glyphs/sotj.png")),
+    ("haab/glyphs/soxj.png", include_bytes!("../assets/haab/glyphs/soxj.png")),
+    ("haab/glyphs/wayeb.png", include_bytes!("../assets/haab/glyphs/wayeb.png")),
+    ("haab/glyphs/wo.png", include_bytes!("../assets/haab/glyphs/wo.png")),
+    ("haab/glyphs/xul.png", include_bytes!("../assets/haab/glyphs/xul.png")),
+    ("haab/glyphs/yax.png", include_bytes!("../assets/haab/glyphs/yax.png")),
+    ("haab/glyphs/yaxin.png", include_bytes!("../assets/haab/glyphs/yaxin.png")),
+];
+
+// Look a glyph up by its path relative to the asset root
+#[cfg(target_arch = "wasm32")]
+fn read_glyph(path: &Path) -> Result<&'static [u8], GlyphError> {
+    BUNDLED_GLYPHS
+        .iter()
+        .find(|(name, _)| path.ends_with(name))
+        .map(|&(_, bytes)| bytes)
+        .ok_or_else(|| GlyphError::NotBundled(path.to_path_buf()))
+}
+
+// Read a glyph and decode it. Any size is accepted since glyphs are scaled
+// to fit when drawn; non-square art is only worth a warning.
+fn load_glyph_image(path: &Path) -> Result<image::DynamicImage, GlyphError> {
+    let bytes = read_glyph(path)?;
+    #[cfg(feature = "svg")]
+    let image = if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg")) {
+        rasterize_svg(&bytes, SVG_RASTER_SIZE)?
+    } else {
+        image::load_from_memory(&bytes)?
+    };
+    #[cfg(not(feature = "svg"))]
+    let image = image::load_from_memory(&bytes)?;
+    if image.width() == 0 || image.height() == 0 {
+        return Err(GlyphError::InvalidDimensions(image.width(), image.height()));
+    }
+    if image.width() != image.height() {
+        tracing::warn!(
+            "Glyph {} is not square ({}x{}); it will be letterboxed",
+            path.display(),
+            image.width(),
+            image.height()
+        );
+    }
+    Ok(image)
+}
+
+/// Where a glyph sits in the atlas image, in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub struct AtlasRect {
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+}
+
+/// The atlas manifest: glyph name (as keyed in `Config`) to rect, per glyph type
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct AtlasManifest {
+    #[serde(default)]
+    pub tzolkin: HashMap<String, AtlasRect>,
+    #[serde(default)]
+    pub haab: HashMap<String, AtlasRect>,
+    #[serde(default)]
+    pub night: HashMap<String, AtlasRect>,
+}
+
+// Cut each glyph in the manifest out of the decoded atlas
+fn slice_atlas(
+    atlas: &image::DynamicImage,
+    manifest: &AtlasManifest,
+) -> Result<Vec<(GlyphType, String, image::DynamicImage)>, GlyphError> {
+    let sections = [
+        (GlyphType::Tzolkin, &manifest.tzolkin),
+        (GlyphType::Haab, &manifest.haab),
+        (GlyphType::Night, &manifest.night),
+    ];
+    let mut glyphs = Vec::new();
+    for (glyph_type, rects) in sections {
+        for (name, rect) in rects {
+            if rect.w == 0 || rect.h == 0 {
+                return Err(GlyphError::InvalidDimensions(rect.w, rect.h));
+            }
+            if rect.x + rect.w > atlas.width() || rect.y + rect.h > atlas.height() {
+                return Err(GlyphError::AtlasBounds(name.clone(), atlas.width(), atlas.height()));
+            }
+            glyphs.push((glyph_type, name.clone(), atlas.crop_imm(rect.x, rect.y, rect.w, rect.h)));
+        }
+    }
+    Ok(glyphs)
+}
+
+// Decode the atlas once and slice it according to its manifest
+fn load_atlas(
+    image_path: &Path,
+    manifest_path: &Path,
+) -> Result<Vec<(GlyphType, String, image::DynamicImage)>, GlyphError> {
+    let manifest = std::fs::read_to_string(manifest_path).map_err(GlyphError::FileError)?;
+    let manifest: AtlasManifest = serde_json::from_str(&manifest)?;
+    let bytes = read_glyph(image_path)?;
+    let atlas = image::load_from_memory(&bytes)?;
+    slice_atlas(&atlas, &manifest)
+}
+
+fn to_color_image(image: &image::DynamicImage) -> ColorImage {
+    let size = [image.width() as usize, image.height() as usize];
+    let image_buffer = image.to_rgba8();
+    let pixels = image_buffer.as_flat_samples();
+    ColorImage::from_rgba_unmultiplied(size, pixels.as_slice())
+}
+
+// Stand-in for a glyph image that couldn't be loaded: the emoji or numeral drawn in the same space
+fn show_fallback_glyph(ui: &mut egui::Ui, text: &str, size: Vec2) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+    ui.painter().text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        text,
+        egui::FontId::proportional(size.y * 0.75),
+        ui.visuals().text_color(),
+    );
+    response
+}
+
+// Hover text for a Tzolk'in day glyph: the name in every name set, its place in the veintena and its meaning
+fn tzolkin_tooltip(index: usize) -> String {
+    let name = |set| day_name(index, set).unwrap_or("?");
+    format!(
+        "{} (K'iche' {}, Classic {})\nDay {} of 20\n{}",
+        name(NameSet::Yucatec),
+        name(NameSet::Kiche),
+        name(NameSet::Classic),
+        index + 1,
+        tzolkin_meaning(index as i32)
+    )
+}
+
+// Hover text for a Haab' month glyph: the name in every name set and its place in the year
+fn haab_tooltip(index: usize) -> String {
+    let name = |set| month_name(index, set).unwrap_or("?");
+    format!(
+        "{} (K'iche' {}, Classic {})\nMonth {} of 19",
+        name(NameSet::Yucatec),
+        name(NameSet::Kiche),
+        name(NameSet::Classic),
+        index + 1
+    )
+}
+
+fn to_mayan_numeral_string(long_count: &LongCount) -> String {
+    format!("{}.{}.{}.{}.{}", 
+        to_mayan_digit(long_count.baktun),
+        to_mayan_digit(long_count.katun),
+        to_mayan_digit(long_count.tun),
+        to_mayan_digit(long_count.uinal),
+        to_mayan_digit(long_count.kin))
+}
+
+fn to_mayan_digit(n: i32) -> String {
+    // Define the Unicode code points for Mayan numerals (0-19)
+    let base_codepoint = 0x1D2E0;  // Starting code point for Mayan numerals
+    let codepoint = base_codepoint + (n as u32);
+    // Convert to a char and then to a string, with debug logging
+    match char::from_u32(codepoint) {
+        Some(c) => {
+            tracing::info!("Generated Mayan numeral for {}: U+{:X}", n, codepoint);
+            c.to_string()
+        },
+        None => {
+            tracing::error!("Failed to create Mayan numeral for {}", n);
+            "?".to_string()
+        }
+    }
+}
+
+/// Progress of the background glyph preload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadingState {
+    Loading { loaded: usize, total: usize },
+    Ready,
+}
+
+// Shared with the preload thread, which counts up as each glyph is decoded
+// and sets aside the ones that fail
+struct PreloadProgress {
+    loaded: AtomicUsize,
+    total: usize,
+    failures: Mutex<Vec<(PathBuf, GlyphError)>>,
+}
+
+impl PreloadProgress {
+    fn new(total: usize) -> Self {
+        Self { loaded: AtomicUsize::new(0), total, failures: Mutex::new(Vec::new()) }
+    }
+}
+
+pub struct GlyphRenderer {
+    cache: Arc<RwLock<TextureCache>>,
+    config: Config,
+    metrics: Arc<Metrics>,
+    ctx: Context, // Egui context
+    progress: Arc<PreloadProgress>,
+}
+
+impl GlyphRenderer {
+    pub fn new(ctx: &Context, config: Config, metrics: Arc<Metrics>) -> Self {
+        let mut renderer = Self {
+            cache: Arc::new(RwLock::new(TextureCache::default())),
+            config,
+            metrics,
+            ctx: ctx.clone(),
+            progress: Arc::new(PreloadProgress::new(0)),
+        };
+        renderer.preload_atlas();
+        renderer.progress = renderer.start_preload();
+        renderer
+    }
+
+    pub fn loading_state(&self) -> LoadingState {
+        let loaded = self.progress.loaded.load(Ordering::Acquire);
+        if loaded < self.progress.total {
+            LoadingState::Loading { loaded, total: self.progress.total }
+        } else {
+            LoadingState::Ready
+        }
+    }
+
+    /// Glyphs the preload has failed to load since the last call. Each glyph
+    /// still renders with its text fallback.
+    pub fn take_failures(&self) -> Vec<(PathBuf, GlyphError)> {
+        std::mem::take(&mut *self.progress.failures.lock().unwrap())
+    }
+
+    // Decode every glyph the atlas didn't cover on the rayon pool, so the
+    // window can open straight away. The browser has no threads, so there
+    // glyphs are still loaded on first use.
+    fn start_preload(&self) -> Arc<PreloadProgress> {
+        let jobs: Vec<(GlyphType, String, PathBuf)> = if cfg!(target_arch = "wasm32") {
+            Vec::new()
+        } else {
+            let cache = self.cache.read().unwrap();
+            [GlyphType::Tzolkin, GlyphType::Haab, GlyphType::Night]
+                .into_iter()
+                .flat_map(|glyph_type| self.glyph_paths(glyph_type).iter().map(move |(n, p)| (glyph_type, n, p)))
+                .filter(|(glyph_type, name, _)| !cache.contains(*glyph_type, name))
+                .map(|(glyph_type, name, path)| (glyph_type, name.clone(), path.clone()))
+                .collect()
+        };
+        let progress = Arc::new(PreloadProgress::new(jobs.len()));
+        if jobs.is_empty() {
+            return progress;
+        }
+
+        let (cache, metrics, ctx) = (Arc::clone(&self.cache), Arc::clone(&self.metrics), self.ctx.clone());
+        let shared = Arc::clone(&progress);
+        let span = tracing::info_span!(
+            "preload_glyphs",
+            glyphs = jobs.len(),
+            failed = tracing::field::Empty,
+            elapsed_ms = tracing::field::Empty
+        );
+        rayon::spawn(move || {
+            use rayon::prelude::*;
+            let start = Instant::now();
+            jobs.par_iter().for_each(|(glyph_type, name, path)| {
+                // Workers run on other threads, so each enters the span itself
+                let _entered = tracing::debug_span!(parent: &span, "load_glyph", glyph = %name).entered();
+                match load_glyph_image(path) {
+                    Ok(image) => {
+                        let texture = ctx.load_texture(name, to_color_image(&image), TextureOptions::default());
+                        cache.write().unwrap().insert(*glyph_type, name.clone(), texture);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to preload glyph {}: {}", path.display(), e);
+                        cache.write().unwrap().mark_failed(*glyph_type, name.clone());
+                        shared.failures.lock().unwrap().push((path.clone(), e));
+                    }
+                }
+                shared.loaded.fetch_add(1, Ordering::Release);
+                ctx.request_repaint();
+            });
+            metrics.record_glyph_load(start.elapsed());
+            span.record("failed", shared.failures.lock().unwrap().len());
+            span.record("elapsed_ms", start.elapsed().as_millis() as u64);
+            span.in_scope(|| tracing::info!("Preloaded {} glyphs", shared.total));
+        });
+        progress
+    }
+
+    // Fill the cache from the glyph atlas, if there is one. Glyphs it doesn't
+    // cover are still loaded from their own files on first use.
+    fn preload_atlas(&self) {
+        if !self.config.atlas_manifest.exists() {
+            return;
+        }
+        let start = Instant::now();
+        let glyphs = match load_atlas(&self.config.atlas_image, &self.config.atlas_manifest) {
+            Ok(glyphs) => glyphs,
+            Err(e) => {
+                if e.is_recoverable() {
+                    tracing::warn!("Failed to load glyph atlas, using individual files: {}", e);
+                } else {
+                    tracing::error!("Glyph atlas is broken, using individual files: {}", e);
+                }
+                return;
+            }
+        };
+        let mut cache = self.cache.write().unwrap();
+        for (glyph_type, name, image) in glyphs {
+            if !self.glyph_paths(glyph_type).contains_key(&name) {
+                tracing::warn!("Atlas glyph {} has no entry in the config", name);
+                continue;
+            }
+            let texture = self.ctx.load_texture(&name, to_color_image(&image), TextureOptions::default());
+            cache.insert(glyph_type, name, texture);
+        }
+        self.metrics.record_glyph_load(start.elapsed());
+        tracing::info!("Loaded glyph atlas in {:?}", start.elapsed());
+    }
+
+    // Configured glyph files of one type, keyed by glyph name
+    fn glyph_paths(&self, glyph_type: GlyphType) -> &HashMap<String, PathBuf> {
+        match glyph_type {
+            GlyphType::Tzolkin => &self.config.tzolkin_glyphs,
+            GlyphType::Haab => &self.config.haab_glyphs,
+            GlyphType::Night => &self.config.night_glyphs,
+        }
+    }
+
+    /// The decoded glyph image itself, for drawing outside egui
+    pub fn glyph_image(&self, glyph_type: GlyphType, name: &str) -> Option<DynamicImage> {
+        let path = self.glyph_paths(glyph_type).get(name)?;
+        load_glyph_image(path)
+            .map_err(|e| tracing::warn!("Failed to load glyph {} for export: {}", path.display(), e))
+            .ok()
+    }
+
+    pub fn get_texture(&self, glyph_type: GlyphType, name: &str) -> Option<TextureHandle> {
+        {
+            let cache = self.cache.read().unwrap();
+            if let Some(texture) = cache.get(glyph_type, name) {
+                return Some(texture.clone());
+            }
+            if cache.has_failed(glyph_type, name) {
+                return None;
+            }
+        }
+        // Not loaded yet, so go to the glyph's file
+        let path = self.glyph_paths(glyph_type).get(name)?;
+        // Load image using the image crate
+        let start = Instant::now();
+        let image = match load_glyph_image(path) {
+            Ok(img) => {
+                tracing::info!("Successfully loaded glyph image: {}", path.display());
+                img
+            }
+            Err(e) => {
+                tracing::error!("Failed to load image at {}: {}", path.display(), e);
+                self.cache.write().unwrap().mark_failed(glyph_type, name.to_string());
+                return None;
+            }
+        };
+        let image_data = to_color_image(&image);
+        let texture = self.ctx.load_texture(name, image_data, TextureOptions::default());
+        self.metrics.record_glyph_load(start.elapsed());
+        self.cache.write().unwrap().insert(glyph_type, name.to_string(), texture.clone());
+        Some(texture)
+    }
+}
+
+// ----- DISPLAY SETTINGS -----
+
+/// Which sections of the calendar window are drawn; everything is on by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayConfig {
+    pub clock: bool,
+    pub long_count: bool,
+    pub tzolkin: bool,
+    pub haab: bool,
+    pub night_lord: bool,
+    pub astronomical: bool,
+    pub historical: bool,
+    pub metrics: bool,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            clock: true,
+            long_count: true,
+            tzolkin: true,
+            haab: true,
+            night_lord: true,
+            astronomical: true,
+            historical: true,
+            metrics: true,
+        }
+    }
+}
+
+impl DisplayConfig {
+    // Checkboxes for each section, used by the settings panel
+    fn show_toggles(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.clock, "Clock");
+        ui.checkbox(&mut self.long_count, "Long Count");
+        ui.checkbox(&mut self.tzolkin, "Tzolk'in");
+        ui.checkbox(&mut self.haab, "Haab'");
+        ui.checkbox(&mut self.night_lord, "Lord of the Night");
+        ui.checkbox(&mut self.astronomical, "Astronomical");
+        ui.checkbox(&mut self.historical, "Historical");
+        ui.checkbox(&mut self.metrics, "Metrics");
+    }
+}
+
+// ----- MAYAN CALENDAR STRUCT & METHODS -----
+
+pub struct MayanCalendar {
+    current_time: chrono::NaiveDateTime,
+    timezone: Timezone,
+    correlation: i32,
+    haab_numbering: HaabNumbering,
+    calendar_data: CalendarData,
+    calculator: ParallelCalendarCalculator,
+    cache: Arc<RwLock<CalendarCache>>,
+    // Where the cache is saved each time the day changes, if anywhere
+    cache_file: Option<PathBuf>,
+    // The day picked in the date picker; None follows today
+    selected_date: Option<NaiveDate>,
+    // The two days side by side in compare mode; None shows the single calendar
+    compare: Option<[CalendarData; 2]>,
+    glyph_renderer: GlyphRenderer,
+    metrics: Arc<Metrics>,
+    display: DisplayConfig,
+    show_settings: bool,
+    // Outcome of the last "Save as image"
+    export_status: Option<String>,
+    // Glyphs that couldn't be loaded, shown so missing assets are easy to spot
+    failed_glyphs: Vec<(PathBuf, GlyphError)>,
+}
+
+impl MayanCalendar {
+    pub fn new(ctx: &Context, display: DisplayConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let metrics = Arc::new(Metrics::new());
+        let config = Config::load()?;
+        let correlation = config.correlation.jdn_offset();
+        let haab_numbering = config.haab_numbering;
+        let timezone = config.timezone;
+        let cache_file = config.cache_file.clone();
+        let glyph_renderer = GlyphRenderer::new(ctx, config, Arc::clone(&metrics));
+        let now = timezone.now();
+        let cache = Arc::new(RwLock::new(load_cache(cache_file.as_deref())));
+        Ok(Self {
+            current_time: now,
+            timezone,
+            correlation,
+            haab_numbering,
+            calendar_data: CalendarData::new(now, correlation),
+            calculator: ParallelCalendarCalculator::new(Arc::clone(&cache), Arc::clone(&metrics), correlation),
+            cache,
+            cache_file,
+            selected_date: None,
+            compare: None,
+            glyph_renderer,
+            metrics,
+            display,
+            show_settings: false,
+            export_status: None,
+            failed_glyphs: Vec::new(),
+        })
+    }
+
+    pub fn update_calendar_data(&mut self) {
+        let date = self.selected_date.unwrap_or_else(|| self.current_time.date());
+        let jdn = gregorian_to_jdn(date.year(), date.month() as i32, date.day() as i32);
+        match self.calculator.calculate_new_data(jdn - self.correlation) {
+            Ok(data) => self.calendar_data = data,
+            Err(e) => {
+                tracing::warn!("Can't show {}: {}", date, e);
+                return;
+            }
+        }
+        self.save_cache();
+        tracing::info!(
+            "Updated astronomical info: Moon Phase: {}, Eclipse: {}, Venus: {}, Year Bearer: {}",
+            self.calendar_data.moon_phase,
+            self.calendar_data.eclipse_status,
+            self.calendar_data.venus_phase,
+            self.calendar_data.year_bearer
+        );
+    }
+
+    pub fn render(&mut self, ctx: &Context) {
+        let start = Instant::now();
+        let desired_size = Vec2::new(128.0, 128.0);
+        egui::TopBottomPanel::top("settings_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.toggle_value(&mut self.show_settings, "⚙ Settings");
+                let mut comparing = self.compare.is_some();
+                if ui.toggle_value(&mut comparing, "⇄ Compare").changed() {
+                    self.compare = comparing.then(|| [self.calendar_data.clone(), self.calendar_data.clone()]);
+                }
+                // The browser has no file system to save to
+                if cfg!(not(target_arch = "wasm32")) && ui.button("💾 Save as image").clicked() {
+                    let path = PathBuf::from(format!("mayan_calendar_{}.png", self.calendar_data.gregorian_date));
+                    self.export_status = Some(match self.export_png(&path) {
+                        Ok(()) => format!("Saved {}", path.display()),
+                        Err(e) => format!("Export failed: {}", e),
+                    });
+                }
+                if let Some(status) = &self.export_status {
+                    ui.label(status);
+                }
+            });
+        });
+        if self.show_settings {
+            egui::SidePanel::right("settings_panel").show(ctx, |ui| {
+                ui.heading("Sections");
+                self.display.show_toggles(ui);
+            });
+        }
+        if self.compare.is_some() {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.heading("Compare Dates");
+                self.render_compare(ui);
+            });
+            self.metrics.record_render(start.elapsed());
+            return;
+        }
+        if let LoadingState::Loading { loaded, total } = self.glyph_renderer.loading_state() {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.heading("Mayan Calendar");
+                ui.label(format!("Loading glyphs {}/{}", loaded, total));
+                ui.add(egui::ProgressBar::new(loaded as f32 / total as f32));
+            });
+            return;
+        }
+        self.failed_glyphs.extend(self.glyph_renderer.take_failures());
+        let display = self.display;
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Mayan Calendar");
+            self.render_date_picker(ui);
+
+            let haab = &self.calendar_data.mayan.haab;
+            if display.haab {
+                let response = match self.glyph_renderer.get_texture(GlyphType::Haab, &haab.yucatec_month.to_lowercase()) {
+                    Some(haab_glyph) => ui.add(egui::Image::new(&haab_glyph).fit_to_exact_size(desired_size)),
+                    None => {
+                        let fallback = haab_glyphs()
+                            .get(haab.yucatec_month.as_str())
+                            .map(|emoji| emoji.to_string())
+                            .unwrap_or_else(|| mayan_numeral(haab.day).to_string());
+                        show_fallback_glyph(ui, &fallback, desired_size)
+                    }
+                };
+                if let Some(index) = HAAB_MONTHS.iter().position(|&m| m == haab.yucatec_month) {
+                    response.on_hover_text(haab_tooltip(index));
+                }
+            }
+            let tzolkin = &self.calendar_data.mayan.tzolkin;
+            if display.tzolkin {
+                let response = match self.glyph_renderer.get_texture(GlyphType::Tzolkin, &tzolkin.yucatec_name.to_lowercase()) {
+                    Some(tzolkin_glyph) => ui.add(egui::Image::new(&tzolkin_glyph).fit_to_exact_size(desired_size)),
+                    None => {
+                        let fallback = tzolkin_glyphs()
+                            .get(tzolkin.yucatec_name.as_str())
+                            .map(|emoji| emoji.to_string())
+                            .unwrap_or_else(|| mayan_numeral(tzolkin.number).to_string());
+                        show_fallback_glyph(ui, &fallback, desired_size)
+                    }
+                };
+                if let Some(index) = tzolkin.veintena_position() {
+                    response.on_hover_text(tzolkin_tooltip(index as usize));
+                }
+            }
+            if display.night_lord {
+                let lord = format!("G{}", lord_of_the_night(self.calendar_data.days_since_creation));
+                match self.glyph_renderer.get_texture(GlyphType::Night, &lord.to_lowercase()) {
+                    Some(night_glyph) => {
+                        ui.add(egui::Image::new(&night_glyph).fit_to_exact_size(desired_size));
+                    }
+                    None => {
+                        show_fallback_glyph(ui, &lord, desired_size);
+                    }
+                }
+            }
+            if display.long_count {
+                ui.horizontal(|ui| {
+                    let mayan_text = to_mayan_numeral_string(&self.calendar_data.mayan.long_count);
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Long Count (Mayan Numerals): {}",
+                            mayan_text
+                        ))
+                        .font(egui::FontId::new(24.0, egui::FontFamily::Monospace))  // Fall back to monospace
+                        .size(24.0)
+                    );
+                    tracing::trace!("Attempting to display Mayan numerals: {}", mayan_text);
+                });
+            }
+            ui.separator();
+            if display.clock {
+                ui.label(format!("Current Time: {}", self.current_time.format("%Y-%m-%d %H:%M:%S")));
+            }
+            if self.selected_date.is_some() {
+                ui.label(format!("Showing: {}", self.calendar_data.gregorian_date));
+            }
+            self.render_maya_date(ui, &self.calendar_data, display);
+            if display.tzolkin {
+                egui::CollapsingHeader::new("Tzolk'in Table").show(ui, |ui| {
+                    render_tzolkin_table(ui, &self.calendar_data.mayan.tzolkin);
+                });
+            }
+            if display.astronomical {
+                self.render_astronomical(ui);
+            }
+            if display.historical {
+                self.render_historical(ui);
+            }
+            if display.long_count {
+                egui::CollapsingHeader::new("Correlations").show(ui, |ui| {
+                    self.render_correlations(ui);
+                });
+            }
+            if display.metrics {
+                egui::CollapsingHeader::new("Performance").show(ui, |ui| {
+                    ui.monospace(self.metrics.report());
+                });
+            }
+            if !self.failed_glyphs.is_empty() {
+                egui::CollapsingHeader::new(format!("⚠ {} glyphs failed to load", self.failed_glyphs.len()))
+                    .show(ui, |ui| {
+                        for (path, error) in &self.failed_glyphs {
+                            ui.label(format!("{}: {}", path.display(), error));
+                        }
+                    });
+            }
+        }); // This closes the egui::CentralPanel::default().show block
+        self.metrics.record_render(start.elapsed());
+    }
+
+    // Persist the cache so the next run starts warm
+    fn save_cache(&self) {
+        if let Some(path) = &self.cache_file {
+            if let Err(e) = self.cache.read().unwrap().save(path) {
+                tracing::warn!("Failed to save calendar cache to {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    // Year/month/day fields for choosing the day shown; "Today" goes back to following the clock
+    fn render_date_picker(&mut self, ui: &mut egui::Ui) {
+        let mut shown = self.calendar_data.gregorian_date;
+        let mut picked = None;
+        let mut step = 0;
+        ui.horizontal(|ui| {
+            if date_fields(ui, &mut shown, self.earliest_date()) {
+                picked = Some(Some(shown));
+            }
+            for (label, days) in [("⏪", -UINAL_STEP), ("◀", -1), ("▶", 1), ("⏩", UINAL_STEP)] {
+                if ui.button(label).clicked() {
+                    step = days;
+                }
+            }
+            if ui.add_enabled(self.selected_date.is_some(), egui::Button::new("Today")).clicked() {
+                picked = Some(None);
+            }
+        });
+        if let Some(date) = picked {
+            self.selected_date = date;
+            self.update_calendar_data();
+        }
+        self.step_date(step);
+    }
+
+    // Arrow keys step a day and Page Up/Down a uinal, unless a text field has the keyboard
+    fn handle_keys(&mut self, ctx: &Context) {
+        if self.compare.is_some() || ctx.wants_keyboard_input() {
+            return;
+        }
+        let step = ctx.input(|input| {
+            [Key::ArrowLeft, Key::ArrowRight, Key::PageUp, Key::PageDown]
+                .into_iter()
+                .filter(|&key| input.key_pressed(key))
+                .filter_map(key_step)
+                .sum()
+        });
+        self.step_date(step);
+    }
+
+    // 0.0.0.0.0 under the current correlation, the first day the pickers offer
+    fn earliest_date(&self) -> NaiveDate {
+        jdn_to_gregorian(self.correlation).unwrap_or(NaiveDate::MIN)
+    }
+
+    // Move the day shown by `days`, which stops following today
+    fn step_date(&mut self, days: i64) {
+        if days == 0 {
+            return;
+        }
+        self.selected_date = Some(stepped_date(self.calendar_data.gregorian_date, days, self.earliest_date()));
+        self.update_calendar_data();
+    }
+
+    // The Long Count, Tzolk'in, Haab' and Lord of the Night of one day
+    fn render_maya_date(&self, ui: &mut egui::Ui, data: &CalendarData, display: DisplayConfig) {
+        if display.long_count {
+            ui.label(format!("Long Count: {}", data.mayan.long_count));
+        }
+        if display.tzolkin {
+            ui.label(format!("Tzolkin: {}", data.mayan.tzolkin.with_kiche()));
+            if let Some(index) = data.mayan.tzolkin.veintena_position() {
+                ui.label(egui::RichText::new(tzolkin_meaning(index)).italics());
+            }
+        }
+        if display.haab {
+            ui.label(format!("Haab: {}", haab_date_numbered(data.days_since_creation, self.haab_numbering)));
+        }
+        if display.night_lord {
+            ui.label(format!("Lord of the Night: G{}", lord_of_the_night(data.days_since_creation)));
+        }
+    }
+
+    // Two date pickers in columns with the distance number between them below
+    fn render_compare(&mut self, ui: &mut egui::Ui) {
+        let Some(mut dates) = self.compare.take() else {
+            return;
+        };
+        let earliest = self.earliest_date();
+        ui.columns(2, |columns| {
+            for ((ui, data), title) in columns.iter_mut().zip(dates.iter_mut()).zip(["First date", "Second date"]) {
+                ui.strong(title);
+                let mut date = data.gregorian_date;
+                if ui.horizontal(|ui| date_fields(ui, &mut date, earliest)).inner {
+                    let jdn = gregorian_to_jdn(date.year(), date.month() as i32, date.day() as i32);
+                    if let Ok(picked) = self.calculator.calculate_new_data(jdn - self.correlation) {
+                        *data = picked;
+                    }
+                }
+                ui.label(format!("Gregorian: {}", data.gregorian_date));
+                self.render_maya_date(ui, data, DisplayConfig::default());
+            }
+        });
+        ui.separator();
+        ui.heading(distance_summary(&dates[0].mayan.long_count, &dates[1].mayan.long_count));
+        self.compare = Some(dates);
+    }
+
+    // The glyphs, Long Count numerals and text lines of the calendar block as shown
+    fn export_contents(&self) -> (Vec<DynamicImage>, String, Vec<String>) {
+        let data = &self.calendar_data;
+        let lord = format!("G{}", lord_of_the_night(data.days_since_creation));
+        let mut glyphs = Vec::new();
+        let mut lines = vec![format!("Date: {}", data.gregorian_date)];
+        if self.display.haab {
+            glyphs.extend(self.glyph_renderer.glyph_image(GlyphType::Haab, &data.mayan.haab.yucatec_month.to_lowercase()));
+        }
+        if self.display.tzolkin {
+            glyphs.extend(self.glyph_renderer.glyph_image(GlyphType::Tzolkin, &data.mayan.tzolkin.yucatec_name.to_lowercase()));
+        }
+        if self.display.night_lord {
+            glyphs.extend(self.glyph_renderer.glyph_image(GlyphType::Night, &lord.to_lowercase()));
+        }
+        if self.display.long_count {
+            lines.push(format!("Long Count: {}", data.mayan.long_count));
+        }
+        if self.display.tzolkin {
+            lines.push(format!("Tzolkin: {}", data.mayan.tzolkin.with_kiche()));
+        }
+        if self.display.haab {
+            lines.push(format!("Haab: {}", haab_date_numbered(data.days_since_creation, self.haab_numbering)));
+        }
+        if self.display.night_lord {
+            lines.push(format!("Lord of the Night: {}", lord));
+        }
+        let numerals = if self.display.long_count {
+            to_mayan_numeral_string(&data.mayan.long_count)
+        } else {
+            String::new()
+        };
+        (glyphs, numerals, lines)
+    }
+
+    /// Saves the calendar block for the day shown as a PNG
+    pub fn export_png(&self, path: &Path) -> Result<(), ExportError> {
+        let (glyphs, numerals, lines) = self.export_contents();
+        render_calendar_image(&glyphs, &numerals, &lines)?.save(path)?;
+        tracing::info!("Exported calendar image to {}", path.display());
+        Ok(())
+    }
+
+    fn render_astronomical(&self, ui: &mut egui::Ui) {
+        ui.label(format!("Moon Phase: {}", self.calendar_data.moon_phase));
+        let lunar = self.calendar_data.lunar_series;
+        ui.label(format!(
+            "Lunar Series: Moon age {} days, lunation {} of 6, {}-day month",
+            lunar.age, lunar.lunation, lunar.month_length
+        ));
+        ui.label(format!("Eclipse Status: {}", self.calendar_data.eclipse_status));
+        ui.label(format!(
+            "Venus Phase: {} (Day {} of 584)",
+            self.calendar_data.venus_phase,
+            self.calendar_data.venus_cycle_day + 1
+        ));
+        ui.label(format!("Year Bearer: {}", self.calendar_data.year_bearer));
+        let station = self.calendar_data.mayan.count_819;
+        ui.label(format!(
+            "819-Day Count: Day {} of 819 ({:?}, {:?})",
+            station.day + 1,
+            station.direction,
+            station.color
+        ));
+    }
+
+    // The day shown under each published correlation, to make their spread visible
+    fn render_correlations(&self, ui: &mut egui::Ui) {
+        let compared = compare_correlations(self.calendar_data.gregorian_date, &NAMED_CORRELATIONS);
+        egui::Grid::new("correlations").striped(true).show(ui, |ui| {
+            for ((name, long_count, round), (_, jdn)) in compared.iter().zip(NAMED_CORRELATIONS) {
+                let label = egui::RichText::new(format!("{} ({})", name, jdn));
+                // Mark the correlation the rest of the window is using
+                ui.label(if jdn == self.correlation { label.strong() } else { label });
+                ui.label(long_count.to_string());
+                ui.label(calendar_round_string(&round.tzolkin, &round.haab));
+                ui.end_row();
+            }
+        });
+    }
+
+    fn render_historical(&self, ui: &mut egui::Ui) {
+        if let Some(event) = &self.calendar_data.historical_event {
+            ui.label(format!("Historical Event: {}", event));
+        }
+    }
+
+    // The clock moves every frame, but the calendar only changes at local midnight
+    fn update_calendar_if_needed(&mut self) {
+        self.current_time = self.timezone.now();
+        if self.selected_date.is_none() && self.current_time.date() != self.calendar_data.gregorian_date {
+            self.update_calendar_data();
+        }
+    }
+} // This closes the impl MayanCalendar block
+
+// The cache saved by an earlier run, or an empty one
+fn load_cache(path: Option<&Path>) -> CalendarCache {
+    if let Some(path) = path.filter(|path| path.exists()) {
+        match CalendarCache::load(path) {
+            Ok(cache) => {
+                tracing::info!("Loaded {} cached days from {}", cache.len(), path.display());
+                return cache;
+            }
+            Err(e) => tracing::warn!("Ignoring unreadable calendar cache {}: {}", path.display(), e),
+        }
+    }
+    CalendarCache::new(NonZeroUsize::new(366).unwrap())
+}
+
+// The 260 days as 20 rows of day names by 13 columns, with `today` highlighted
+fn render_tzolkin_table(ui: &mut egui::Ui, today: &TzolkinDate) {
+    let table = tzolkin_table();
+    egui::Grid::new("tzolkin_table").spacing([6.0, 2.0]).show(ui, |ui| {
+        for (j, name) in TZOLKIN_NAMES.iter().enumerate() {
+            ui.label(*name);
+            for column in &table {
+                let day = &column[j];
+                let text = egui::RichText::new(day.number.to_string()).monospace();
+                if day == today {
+                    ui.label(text.strong().background_color(ui.visuals().selection.bg_fill));
+                } else {
+                    ui.label(text);
+                }
+            }
+            ui.end_row();
+        }
+    });
+}
+
+// Days moved by one press of the ⏪/⏩ buttons or Page Up/Down: one uinal
+const UINAL_STEP: i64 = 20;
+
+// The latest year the date fields and stepping reach
+const LATEST_YEAR: i32 = 9999;
+
+// How far a navigation key moves the day shown
+fn key_step(key: Key) -> Option<i64> {
+    match key {
+        Key::ArrowLeft => Some(-1),
+        Key::ArrowRight => Some(1),
+        Key::PageUp => Some(-UINAL_STEP),
+        Key::PageDown => Some(UINAL_STEP),
+        _ => None,
+    }
+}
+
+// `date` moved by `days`, kept between `earliest` (0.0.0.0.0) and the end of LATEST_YEAR
+fn stepped_date(date: NaiveDate, days: i64, earliest: NaiveDate) -> NaiveDate {
+    let latest = NaiveDate::from_ymd_opt(LATEST_YEAR, 12, 31).unwrap();
+    let stepped = chrono::Duration::try_days(days).and_then(|days| date.checked_add_signed(days));
+    stepped.unwrap_or(if days < 0 { earliest } else { latest }).clamp(earliest, latest)
+}
+
+// Year/month/day fields editing `date`; true when it changed
+fn date_fields(ui: &mut egui::Ui, date: &mut NaiveDate, earliest: NaiveDate) -> bool {
+    let (mut year, mut month, mut day) = (date.year(), date.month(), date.day());
+    ui.label("Date:");
+    let mut changed = ui.add(egui::DragValue::new(&mut year).clamp_range(-3113..=LATEST_YEAR)).changed();
+    changed |= ui.add(egui::DragValue::new(&mut month).clamp_range(1..=12)).changed();
+    changed |= ui.add(egui::DragValue::new(&mut day).clamp_range(1..=31)).changed();
+    if changed {
+        *date = clamp_date(year, month, day, earliest);
+    }
+    changed
+}
+
+// The distance number from `a` to `b` and which way it runs, e.g.
+// "Distance: 0.4.1.10.18 (29378 days later)"
+fn distance_summary(a: &LongCount, b: &LongCount) -> String {
+    let days = days_between(a, b);
+    let direction = match days.signum() {
+        1 => "later",
+        -1 => "earlier",
+        _ => return format!("Distance: {} (the same day)", distance(a, b)),
+    };
+    format!("Distance: {} ({} days {})", distance(a, b), days.abs(), direction)
+}
+
+// The nearest real date to the picker's fields: the day is pulled back to the
+// end of its month, and nothing earlier than `earliest` (0.0.0.0.0) is allowed
+fn clamp_date(year: i32, month: u32, day: u32, earliest: NaiveDate) -> NaiveDate {
+    let date = (1..=day.clamp(1, 31))
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month.clamp(1, 12), day))
+        .unwrap_or(earliest);
+    date.max(earliest)
+}
+
+// --------- MAYAN CALENDAR APP METHOD ----------
+
+// Time until the next whole second when the clock is showing, else until midnight
+fn until_next_refresh(now: chrono::NaiveDateTime, clock: bool) -> std::time::Duration {
+    let next = if clock {
+        now.with_nanosecond(0).unwrap() + chrono::Duration::seconds(1)
+    } else {
+        (now.date() + chrono::Duration::days(1)).and_time(chrono::NaiveTime::MIN)
+    };
+    (next - now).to_std().unwrap_or_default()
+}
+
+impl App for MayanCalendar {
+    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        self.update_calendar_if_needed();
+        self.handle_keys(ctx);
+        self.render(ctx);
+        // Sleep until something on screen changes; input still wakes the UI
+        ctx.request_repaint_after(until_next_refresh(
+            self.current_time,
+            self.display.clock,
+        ));
+    }
+}
+
+// ----- IMAGE EXPORT -----
+
+const MAYAN_NUMERAL_FONT: &[u8] = include_bytes!("../assets/fonts/NotoSansMayanNumerals-Regular.ttf");
+const EXPORT_WIDTH: u32 = 640;
+const EXPORT_GLYPH_SIZE: u32 = 128;
+const EXPORT_MARGIN: u32 = 16;
+const EXPORT_NUMERAL_SIZE: f32 = 48.0;
+const EXPORT_TEXT_SIZE: f32 = 22.0;
+const EXPORT_BACKGROUND: Rgba<u8> = Rgba([245, 236, 215, 255]);
+const EXPORT_INK: Rgba<u8> = Rgba([40, 30, 20, 255]);
+
+/// Why the calendar couldn't be saved as an image
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    #[error("No font available for the exported text")]
+    MissingFont,
+    #[error("Invalid font: {0}")]
+    InvalidFont(#[from] ab_glyph::InvalidFont),
+    #[error("Failed to write image: {0}")]
+    ImageError(#[from] image::ImageError),
+}
+
+// Draw one line of text with its top-left corner at (x, y), clipped to the image
+fn draw_text(image: &mut RgbaImage, font: &FontArc, size: f32, x: f32, y: f32, text: &str) {
+    let scaled = font.as_scaled(size);
+    let mut caret = x;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            caret += scaled.kern(previous, id);
+        }
+        previous = Some(id);
+        let glyph = id.with_scale_and_position(size, ab_glyph::point(caret, y + scaled.ascent()));
+        caret += scaled.h_advance(id);
+        let Some(outline) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outline.px_bounds();
+        outline.draw(|gx, gy, coverage| {
+            let (px, py) = (bounds.min.x as i64 + gx as i64, bounds.min.y as i64 + gy as i64);
+            if px < 0 || py < 0 || px >= image.width() as i64 || py >= image.height() as i64 {
+                return;
+            }
+            let pixel = image.get_pixel_mut(px as u32, py as u32);
+            for (channel, ink) in pixel.0.iter_mut().zip(EXPORT_INK.0).take(3) {
+                *channel = (*channel as f32 * (1.0 - coverage) + ink as f32 * coverage).round() as u8;
+            }
+        });
+    }
+}
+
+/// Draws a calendar block without going through egui, so it also works
+/// headless: the glyphs in a row, the Long Count in Maya numerals, then one
+/// line of text each
+pub fn render_calendar_image(glyphs: &[DynamicImage], numerals: &str, lines: &[String]) -> Result<RgbaImage, ExportError> {
+    let text_font = egui::FontDefinitions::default()
+        .font_data
+        .get("Ubuntu-Light")
+        .map(|data| FontArc::try_from_vec(data.font.to_vec()))
+        .ok_or(ExportError::MissingFont)??;
+    let numeral_font = FontArc::try_from_slice(MAYAN_NUMERAL_FONT)?;
+
+    let glyph_row = if glyphs.is_empty() { 0 } else { EXPORT_GLYPH_SIZE + EXPORT_MARGIN };
+    let numeral_row = if numerals.is_empty() { 0 } else { EXPORT_NUMERAL_SIZE as u32 + EXPORT_MARGIN };
+    let line_height = (EXPORT_TEXT_SIZE * 1.4) as u32;
+    let height = EXPORT_MARGIN + glyph_row + numeral_row + line_height * lines.len() as u32 + EXPORT_MARGIN;
+    let mut image = RgbaImage::from_pixel(EXPORT_WIDTH, height, EXPORT_BACKGROUND);
+
+    // Glyphs keep their aspect ratio, centred in their square
+    let mut x = EXPORT_MARGIN;
+    for glyph in glyphs {
+        let glyph = glyph.resize(EXPORT_GLYPH_SIZE, EXPORT_GLYPH_SIZE, image::imageops::FilterType::Triangle);
+        let offset_x = (EXPORT_GLYPH_SIZE - glyph.width()) / 2;
+        let offset_y = (EXPORT_GLYPH_SIZE - glyph.height()) / 2;
+        image::imageops::overlay(&mut image, &glyph.to_rgba8(), (x + offset_x) as i64, (EXPORT_MARGIN + offset_y) as i64);
+        x += EXPORT_GLYPH_SIZE + EXPORT_MARGIN;
+    }
+    let mut y = (EXPORT_MARGIN + glyph_row) as f32;
+    if !numerals.is_empty() {
+        draw_text(&mut image, &numeral_font, EXPORT_NUMERAL_SIZE, EXPORT_MARGIN as f32, y, numerals);
+        y += numeral_row as f32;
+    }
+    for line in lines {
+        draw_text(&mut image, &text_font, EXPORT_TEXT_SIZE, EXPORT_MARGIN as f32, y, line);
+        y += line_height as f32;
+    }
+    Ok(image)
+}
+
+// ----- CONFIGURE FONTS & RUN -----
+
+fn configure_fonts(ctx: &Context) -> Result<(), Box<dyn std::error::Error>> {
+    let mut fonts = egui::FontDefinitions::default();
+    // Load font with corrected path and add debug logging
+    let font_data = MAYAN_NUMERAL_FONT;
+    tracing::info!("Font data loaded successfully, size: {} bytes", font_data.len());
+    // Add font with a unique name
+    fonts.font_data.insert(
+        "mayan_numerals".to_owned(),
+        egui::FontData::from_static(font_data)
+    );
+    // Register for both Proportional and Monospace families
+    fonts.families.get_mut(&egui::FontFamily::Proportional)
+        .unwrap()
+        .insert(0, "mayan_numerals".to_owned());
+    
+    fonts.families.get_mut(&egui::FontFamily::Monospace)
+        .unwrap()
+        .insert(0, "mayan_numerals".to_owned());
+    // Create a dedicated Mayan family
+    fonts.families.insert(
+        egui::FontFamily::Name("mayan".into()),
+        vec!["mayan_numerals".to_owned()]
+    );
+    ctx.set_fonts(fonts);
+    tracing::info!("Font configuration completed");
+    Ok(())
+}
+
+// Open the calendar window with the given sections and block until it is closed
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run(display: DisplayConfig) -> Result<(), eframe::Error> {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([800.0, 600.0]),
+        vsync: true,
+        ..Default::default()
+    };
+    eframe::run_native(
+        "Mayan Calendar",
+        options,
+        Box::new(move |cc| create_app(cc, display)),
+    )
+}
+
+// Start the calendar on the page's canvas; the browser drives it from here
+#[cfg(target_arch = "wasm32")]
+pub fn run(display: DisplayConfig) -> Result<(), eframe::Error> {
+    wasm_bindgen_futures::spawn_local(async move {
+        let result = eframe::WebRunner::new()
+            .start(
+                "mayan_calendar_canvas",
+                eframe::WebOptions::default(),
+                Box::new(move |cc| create_app(cc, display)),
+            )
+            .await;
+        if let Err(e) = result {
+            tracing::error!("Failed to start web app: {:?}", e);
+        }
+    });
+    Ok(())
+}
+
+fn create_app(cc: &eframe::CreationContext<'_>, display: DisplayConfig) -> Box<dyn App> {
+    // Configure fonts before creating the app
+    configure_fonts(&cc.egui_ctx).expect("Failed to configure fonts");
+    let app = MayanCalendar::new(&cc.egui_ctx, display).unwrap();
+    Box::new(app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glyph_preload_reaches_ready() {
+        let config = Config::with_asset_root(Path::new(env!("CARGO_MANIFEST_DIR")).join("assets"));
+        let total = config.tzolkin_glyphs.len() + config.haab_glyphs.len() + config.night_glyphs.len();
+        let renderer = GlyphRenderer::new(&Context::default(), config, Arc::new(Metrics::new()));
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+        while let LoadingState::Loading { loaded, total: expected } = renderer.loading_state() {
+            assert_eq!(expected, total);
+            assert!(loaded < total);
+            assert!(std::time::Instant::now() < deadline, "preload stalled at {}/{}", loaded, total);
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        // Missing glyphs are set aside rather than stopping the preload
+        let failures = renderer.take_failures();
+        assert!(failures.iter().any(|(path, _)| path.ends_with("night/glyphs/g9.png")));
+        assert!(failures.iter().all(|(path, error)| error.is_recoverable() && !path.exists()));
+        assert!(renderer.take_failures().is_empty());
+        // Glyphs that exist on disk are already cached
+        let cache = renderer.cache.read().unwrap();
+        assert!(cache.contains(GlyphType::Tzolkin, "ajaw"));
+        assert!(cache.contains(GlyphType::Haab, "pop"));
+        assert!(!cache.contains(GlyphType::Night, "g9"));
+        // ...and the ones that failed aren't retried on every frame
+        assert!(cache.has_failed(GlyphType::Night, "g9"));
+        assert!(!cache.has_failed(GlyphType::Tzolkin, "ajaw"));
+        drop(cache);
+        assert!(renderer.get_texture(GlyphType::Night, "g9").is_none());
+        assert!(renderer.get_texture(GlyphType::Tzolkin, "ajaw").is_some());
+    }
+
+    #[test]
+    fn test_render_calendar_image() {
+        let red = Rgba([200, 0, 0, 255]);
+        let glyph = DynamicImage::ImageRgba8(RgbaImage::from_pixel(64, 32, red));
+        let lines = vec!["Long Count: 13.0.0.0.0".to_string(), "Tzolkin: 4 Ajaw".to_string()];
+        let numerals = to_mayan_numeral_string(&LongCount::from_days(1_872_000));
+        let image = render_calendar_image(&[glyph], &numerals, &lines).unwrap();
+        assert_eq!(image.width(), EXPORT_WIDTH);
+        assert_eq!(image.height(), 16 + 144 + 64 + 30 * 2 + 16);
+        // The glyph is scaled up to fill the width of its square
+        assert_eq!(*image.get_pixel(16 + 64, 16 + 64), red);
+        assert_eq!(*image.get_pixel(16 + 64, 20), EXPORT_BACKGROUND);
+        // Both the numerals and the text leave ink behind
+        let inked = |rows: std::ops::Range<u32>| {
+            rows.flat_map(|y| (0..EXPORT_WIDTH).map(move |x| (x, y)))
+                .any(|(x, y)| image.get_pixel(x, y)[0] < 128)
+        };
+        assert!(inked(160..224));
+        assert!(inked(224..284));
+        assert!(!inked(284..300));
+    }
+
+    #[test]
+    fn test_clamp_date() {
+        let creation = NaiveDate::from_ymd_opt(-3113, 8, 11).unwrap();
+        let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(clamp_date(2012, 12, 21, creation), ymd(2012, 12, 21));
+        assert_eq!(clamp_date(2023, 2, 31, creation), ymd(2023, 2, 28));
+        assert_eq!(clamp_date(2024, 2, 30, creation), ymd(2024, 2, 29));
+        assert_eq!(clamp_date(2024, 13, 0, creation), ymd(2024, 12, 1));
+        assert_eq!(clamp_date(-3113, 1, 1, creation), creation);
+    }
+
+    #[test]
+    fn test_distance_summary() {
+        let birth: LongCount = "9.8.9.13.0".parse().unwrap();
+        let death: LongCount = "9.12.11.5.18".parse().unwrap();
+        assert_eq!(distance_summary(&birth, &death), "Distance: 0.4.1.10.18 (29378 days later)");
+        assert_eq!(distance_summary(&death, &birth), "Distance: 0.4.1.10.18 (29378 days earlier)");
+        assert_eq!(distance_summary(&birth, &birth), "Distance: 0.0.0.0.0 (the same day)");
+    }
+
+    #[test]
+    fn test_glyph_tooltips() {
+        assert_eq!(
+            tzolkin_tooltip(19),
+            "Ajaw (K'iche' Ajpu, Classic Ahau)\nDay 20 of 20\nthe sun and the hunter: heroism"
+        );
+        assert!(tzolkin_tooltip(0).starts_with("Imix (K'iche' Imox, Classic Imix)\nDay 1 of 20\n"));
+        assert_eq!(haab_tooltip(17), "Kumk'u (K'iche' Kumk'u, Classic Cumku)\nMonth 18 of 19");
+        assert_eq!(haab_tooltip(18), "Wayeb' (K'iche' Wayeb', Classic Uayeb)\nMonth 19 of 19");
+    }
+
+    #[test]
+    fn test_key_step() {
+        assert_eq!(key_step(Key::ArrowLeft), Some(-1));
+        assert_eq!(key_step(Key::ArrowRight), Some(1));
+        assert_eq!(key_step(Key::PageUp), Some(-20));
+        assert_eq!(key_step(Key::PageDown), Some(20));
+        assert_eq!(key_step(Key::Enter), None);
+    }
+
+    #[test]
+    fn test_stepped_date() {
+        let creation = NaiveDate::from_ymd_opt(-3113, 8, 11).unwrap();
+        let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(stepped_date(ymd(2012, 12, 21), 1, creation), ymd(2012, 12, 22));
+        assert_eq!(stepped_date(ymd(2012, 12, 21), -20, creation), ymd(2012, 12, 1));
+        assert_eq!(stepped_date(ymd(2024, 2, 28), 1, creation), ymd(2024, 2, 29));
+        // Clamped at 0.0.0.0.0 and the end of the last year the fields allow
+        assert_eq!(stepped_date(creation, -1, creation), creation);
+        assert_eq!(stepped_date(ymd(9999, 12, 31), 20, creation), ymd(9999, 12, 31));
+        assert_eq!(stepped_date(ymd(2000, 1, 1), i64::MAX, creation), ymd(9999, 12, 31));
+        assert_eq!(stepped_date(ymd(2000, 1, 1), i64::MIN, creation), creation);
+    }
+
+    #[test]
+    fn test_until_next_refresh() {
+        let now = chrono::NaiveDate::from_ymd_opt(2024, 3, 20)
+            .unwrap()
+            .and_hms_milli_opt(23, 59, 58, 250)
+            .unwrap();
+        assert_eq!(until_next_refresh(now, true), std::time::Duration::from_millis(750));
+        assert_eq!(until_next_refresh(now, false), std::time::Duration::from_millis(1750));
+    }
+
+    #[test]
+    fn test_slice_atlas() {
+        // Two 2x2 glyphs side by side: red on the left, blue on the right
+        let atlas = image::RgbaImage::from_fn(4, 2, |x, _| {
+            if x < 2 { image::Rgba([255, 0, 0, 255]) } else { image::Rgba([0, 0, 255, 255]) }
+        });
+        let atlas = image::DynamicImage::ImageRgba8(atlas);
+        let manifest: AtlasManifest = serde_json::from_str(
+            r#"{
+                "tzolkin": { "imix": { "x": 0, "y": 0, "w": 2, "h": 2 } },
+                "haab": { "pop": { "x": 2, "y": 0, "w": 2, "h": 2 } },
+                "night": { "g9": { "x": 0, "y": 0, "w": 2, "h": 2 } }
+            }"#,
+        )
+        .unwrap();
+        let glyphs = slice_atlas(&atlas, &manifest).unwrap();
+        assert_eq!(glyphs.len(), 3);
+        assert_eq!((glyphs[2].0, glyphs[2].1.as_str()), (GlyphType::Night, "g9"));
+        let (glyph_type, name, image) = &glyphs[1];
+        assert_eq!((*glyph_type, name.as_str()), (GlyphType::Haab, "pop"));
+        assert_eq!(image.to_rgba8().get_pixel(0, 0).0, [0, 0, 255, 255]);
+
+        // Rects must lie inside the atlas
+        let manifest: AtlasManifest =
+            serde_json::from_str(r#"{ "haab": { "pop": { "x": 3, "y": 0, "w": 2, "h": 2 } } }"#).unwrap();
+        assert!(matches!(slice_atlas(&atlas, &manifest), Err(GlyphError::AtlasBounds(..))));
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn test_rasterize_svg() {
+        let svg = br##"<svg xmlns="http://www.w3.org/2000/svg" width="64" height="32">
+            <rect width="64" height="32" fill="#ff0000"/>
+        </svg>"##;
+        let image = rasterize_svg(svg, SVG_RASTER_SIZE).unwrap().to_rgba8();
+        assert_eq!(image.dimensions(), (256, 128));
+        assert_eq!(image.get_pixel(128, 64).0, [255, 0, 0, 255]);
+        assert!(rasterize_svg(b"not svg", SVG_RASTER_SIZE).is_err());
+    }
+}
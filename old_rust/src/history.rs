@@ -0,0 +1,312 @@
+use chrono::NaiveDate;
+
+use crate::correlation::jdn_to_gregorian;
+use crate::long_count::LongCount;
+
+/// A dated event from the Maya record. `long_count` is the date as inscribed,
+/// where the source carries one; `jdn` uses the GMT correlation (584283).
+/// Spanish records from after October 1582 are already Gregorian; anything
+/// earlier was written in the Julian calendar and goes through `julian_to_jdn`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoricalEvent {
+    pub jdn: i32,
+    pub long_count: Option<LongCount>,
+    pub title: &'static str,
+    pub site: &'static str,
+}
+
+/// Every known event, in date order
+pub static HISTORICAL_EVENTS: &[HistoricalEvent] = &[
+    HistoricalEvent {
+        jdn: 584283,
+        long_count: Some(LongCount::new(0, 0, 0, 0, 0)),
+        title: "🌎 The Maya creation date (0.0.0.0.0)",
+        site: "Quiriguá, Stela C",
+    },
+    HistoricalEvent {
+        jdn: 1708616,
+        long_count: Some(LongCount::new(7, 16, 3, 2, 13)),
+        title: "📜 Earliest Long Count Date Found (7.16.3.2.13)",
+        site: "Chiapa de Corzo, Stela 2",
+    },
+    HistoricalEvent {
+        jdn: 1859135,
+        long_count: Some(LongCount::new(8, 17, 1, 4, 12)),
+        title: "⚔️ Teotihuacan Arrival at Tikal",
+        site: "Tikal, Stela 31",
+    },
+    HistoricalEvent {
+        // 426 CE, dated to the year only
+        jdn: 1876654,
+        long_count: None,
+        title: "🏛️ Dynasty of Copán Founded",
+        site: "Copán",
+    },
+    HistoricalEvent {
+        jdn: 1876900,
+        long_count: Some(LongCount::new(8, 19, 10, 10, 17)),
+        title: "👑 Accession of K'inich Yax K'uk' Mo' at Copán",
+        site: "Copán, Altar Q",
+    },
+    HistoricalEvent {
+        jdn: 1926445,
+        long_count: Some(LongCount::new(9, 6, 8, 4, 2)),
+        title: "⚔️ Defeat of Tikal",
+        site: "Caracol, Altar 21",
+    },
+    HistoricalEvent {
+        jdn: 1941383,
+        long_count: Some(LongCount::new(9, 8, 9, 13, 0)),
+        title: "👑 Birth of K'inich Janaab' Pakal I",
+        site: "Palenque, Temple of the Inscriptions",
+    },
+    HistoricalEvent {
+        jdn: 1970279,
+        long_count: Some(LongCount::new(9, 12, 9, 17, 16)),
+        title: "👑 Accession of Jasaw Chan K'awiil I",
+        site: "Tikal, Temple I",
+    },
+    HistoricalEvent {
+        jdn: 1970761,
+        long_count: Some(LongCount::new(9, 12, 11, 5, 18)),
+        title: "🗿 Death of K'inich Janaab' Pakal I",
+        site: "Palenque, Temple of the Inscriptions",
+    },
+    HistoricalEvent {
+        jdn: 1973815,
+        long_count: Some(LongCount::new(9, 12, 19, 14, 12)),
+        title: "🏛️ Dedication of the Cross Group at Palenque",
+        site: "Palenque, Temple of the Cross",
+    },
+    HistoricalEvent {
+        // 751 CE, dated to the year only
+        jdn: 1995357,
+        long_count: None,
+        title: "🏛️ Uxmal Emerges as a Major Power",
+        site: "Uxmal",
+    },
+    HistoricalEvent {
+        // December 869, soon after Tikal's last dated monument
+        jdn: 2038791,
+        long_count: None,
+        title: "🏛️ Tikal Abandoned",
+        site: "Tikal",
+    },
+    HistoricalEvent {
+        // 987 CE, dated to the year only
+        jdn: 2081555,
+        long_count: None,
+        title: "🏰 Toltec-Maya Rule in Chichen Itzá Begins",
+        site: "Chichen Itzá",
+    },
+    HistoricalEvent {
+        // 1200 CE, dated to the year only
+        jdn: 2159351,
+        long_count: None,
+        title: "🔺 Decline of Chichen Itzá",
+        site: "Chichen Itzá",
+    },
+    HistoricalEvent {
+        // 1 August 1511, Julian
+        jdn: 2273163,
+        long_count: None,
+        title: "⚔️ Spanish Make First Contact with the Maya",
+        site: "Yucatán coast",
+    },
+    HistoricalEvent {
+        // 13 March 1697, Gregorian
+        jdn: 2340949,
+        long_count: None,
+        title: "⚔️ Spanish Conquest of Nojpetén",
+        site: "Nojpetén (Tayasal), Petén",
+    },
+    HistoricalEvent {
+        jdn: 2456283,
+        long_count: Some(LongCount::new(13, 0, 0, 0, 0)),
+        title: "🌅 Completion of the 13th Bak'tun",
+        site: "Tortuguero, Monument 6",
+    },
+];
+
+/// Retrieves historical event for a given Julian Day Number
+pub fn historical_event(jdn: i32) -> Option<&'static str> {
+    HISTORICAL_EVENTS
+        .iter()
+        .find(|event| event.jdn == jdn)
+        .map(|event| event.title)
+}
+
+/// The Gregorian date of the event titled `title`, with or without its
+/// leading emoji and ignoring ASCII case. Inscribed Long Counts are placed
+/// using `correlation`; dates from Spanish records don't depend on it.
+/// None if no event has that title or the correlation pushes it off the calendar.
+pub fn event_gregorian_date(title: &str, correlation: i32) -> Option<NaiveDate> {
+    let title = title.trim();
+    let event = HISTORICAL_EVENTS.iter().find(|event| {
+        let text = event.title.trim_start_matches(|c: char| !c.is_alphanumeric());
+        event.title.eq_ignore_ascii_case(title) || text.eq_ignore_ascii_case(title)
+    })?;
+    let date = match event.long_count {
+        Some(long_count) => long_count.to_gregorian(correlation),
+        None => jdn_to_gregorian(event.jdn),
+    };
+    date.ok()
+}
+
+/// Events within `window_days` either side of `jdn`, nearest first, each with
+/// its signed offset in days (positive means the event is still ahead)
+pub fn historical_events_near(jdn: i32, window_days: i32) -> Vec<(&'static str, i32)> {
+    let mut events: Vec<(&'static str, i32)> = HISTORICAL_EVENTS
+        .iter()
+        .map(|event| (event.title, event.jdn - jdn))
+        .filter(|&(_, offset)| offset.abs() <= window_days)
+        .collect();
+    events.sort_by_key(|&(event, offset)| (offset.abs(), offset, event));
+    events
+}
+
+/// Events whose Long Count falls in `baktun`, in date order. Inscribed Long
+/// Counts are used as-is; other events are placed using `correlation`.
+pub fn events_in_baktun(baktun: i32, correlation: i32) -> Vec<&'static HistoricalEvent> {
+    let mut events: Vec<&'static HistoricalEvent> = HISTORICAL_EVENTS
+        .iter()
+        .filter(|event| {
+            let long_count = event
+                .long_count
+                .unwrap_or_else(|| LongCount::from_days(event.jdn - correlation));
+            long_count.baktun == baktun
+        })
+        .collect();
+    events.sort_by_key(|event| event.jdn);
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date_utils::{gregorian_to_jdn, CalendarSystem};
+
+    const GMT: i32 = 584283;
+
+    #[test]
+    fn test_historical_events_match_long_counts() {
+        for event in HISTORICAL_EVENTS {
+            let days = event.jdn - GMT;
+            assert_eq!(LongCount::from_days(days).to_days(), days, "{}", event.title);
+            if let Some(long_count) = event.long_count {
+                assert_eq!(long_count.to_days(), days, "{}", event.title);
+            }
+        }
+        assert!(HISTORICAL_EVENTS.windows(2).all(|pair| pair[0].jdn < pair[1].jdn));
+
+        // Events with no inscription are dated from the civil date on record,
+        // in the calendar it was written in, and land after the Classic-period
+        // Long Counts they follow
+        let (gregorian, spanish) = (CalendarSystem::Gregorian, CalendarSystem::Historical);
+        let recorded = [
+            ("🏛️ Dynasty of Copán Founded", (426, 1, 1), gregorian, "8.19.9.16.11"),
+            ("🏛️ Uxmal Emerges as a Major Power", (751, 1, 1), gregorian, "9.15.19.11.14"),
+            ("🏛️ Tikal Abandoned", (869, 12, 1), gregorian, "10.2.0.5.8"),
+            ("🏰 Toltec-Maya Rule in Chichen Itzá Begins", (987, 1, 1), gregorian, "10.7.19.1.12"),
+            ("🔺 Decline of Chichen Itzá", (1200, 1, 1), gregorian, "10.18.15.3.8"),
+            ("⚔️ Spanish Make First Contact with the Maya", (1511, 8, 1), spanish, "11.14.11.6.0"),
+            ("⚔️ Spanish Conquest of Nojpetén", (1697, 3, 13), spanish, "12.3.19.11.6"),
+        ];
+        let uninscribed: Vec<&str> =
+            HISTORICAL_EVENTS.iter().filter(|event| event.long_count.is_none()).map(|event| event.title).collect();
+        assert_eq!(uninscribed, recorded.map(|(title, _, _, _)| title));
+        for (title, (year, month, day), calendar, long_count) in recorded {
+            let event = HISTORICAL_EVENTS.iter().find(|event| event.title == title).unwrap();
+            assert_eq!(event.jdn, calendar.to_jdn(year, month, day), "{}", title);
+            assert_eq!(LongCount::from_days(event.jdn - GMT).to_string(), long_count, "{}", title);
+        }
+    }
+
+    #[test]
+    fn test_historical_events_near() {
+        let pakal = "👑 Birth of K'inich Janaab' Pakal I";
+        assert_eq!(historical_events_near(1941383 - 12, 15), vec![(pakal, 12)]);
+        assert_eq!(historical_events_near(1941383 + 3, 15), vec![(pakal, -3)]);
+        assert!(historical_events_near(1941383 - 16, 15).is_empty());
+
+        // Sorted by distance, whichever side the event falls on
+        let near = historical_events_near(1970500, 500);
+        assert_eq!(
+            near,
+            vec![
+                ("👑 Accession of Jasaw Chan K'awiil I", -221),
+                ("🗿 Death of K'inich Janaab' Pakal I", 261),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_events_in_baktun() {
+        let titles = |baktun| -> Vec<&str> {
+            events_in_baktun(baktun, GMT).iter().map(|event| event.title).collect()
+        };
+        // The Classic period sits in bak'tuns 8 and 9
+        assert_eq!(
+            titles(8),
+            vec![
+                "⚔️ Teotihuacan Arrival at Tikal",
+                "🏛️ Dynasty of Copán Founded",
+                "👑 Accession of K'inich Yax K'uk' Mo' at Copán",
+            ]
+        );
+        let classic = events_in_baktun(9, GMT);
+        assert_eq!(classic.len(), 6);
+        assert!(classic.windows(2).all(|pair| pair[0].jdn < pair[1].jdn));
+        assert!(classic.iter().all(|event| LongCount::from_days(event.jdn - GMT).baktun == 9));
+
+        assert_eq!(titles(7), vec!["📜 Earliest Long Count Date Found (7.16.3.2.13)"]);
+        assert_eq!(
+            titles(10),
+            vec![
+                "🏛️ Tikal Abandoned",
+                "🏰 Toltec-Maya Rule in Chichen Itzá Begins",
+                "🔺 Decline of Chichen Itzá",
+            ]
+        );
+        assert_eq!(titles(11), vec!["⚔️ Spanish Make First Contact with the Maya"]);
+        assert_eq!(titles(12), vec!["⚔️ Spanish Conquest of Nojpetén"]);
+        assert_eq!(titles(13), vec!["🌅 Completion of the 13th Bak'tun"]);
+        assert!(titles(14).is_empty());
+    }
+
+    #[test]
+    fn test_event_gregorian_date() {
+        let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+        // 11 August 3114 BCE is astronomical year -3113
+        assert_eq!(event_gregorian_date("🌎 The Maya creation date (0.0.0.0.0)", GMT), ymd(-3113, 8, 11));
+        assert_eq!(event_gregorian_date("the maya creation date (0.0.0.0.0)", GMT), ymd(-3113, 8, 11));
+        assert_eq!(event_gregorian_date("Birth of K'inich Janaab' Pakal I", GMT), ymd(603, 3, 24));
+        assert_eq!(event_gregorian_date("Birth of K'inich Janaab' Pakal I", 584285), ymd(603, 3, 26));
+        // Spanish records give the date directly, whatever the correlation;
+        // 1 August 1511 (Julian) is the 11th in the proleptic Gregorian calendar
+        assert_eq!(event_gregorian_date("Spanish Make First Contact with the Maya", GMT), ymd(1511, 8, 11));
+        assert_eq!(event_gregorian_date("Spanish Conquest of Nojpetén", GMT), ymd(1697, 3, 13));
+        assert_eq!(event_gregorian_date("Spanish Conquest of Nojpetén", 622261), ymd(1697, 3, 13));
+        assert_eq!(event_gregorian_date("Tikal Abandoned", GMT), ymd(869, 12, 1));
+        assert_eq!(event_gregorian_date("Abandonment of Tikal", GMT), None);
+        assert_eq!(event_gregorian_date("", GMT), None);
+        assert_eq!(event_gregorian_date("Birth of K'inich Janaab' Pakal I", i32::MAX), None);
+
+        for event in HISTORICAL_EVENTS {
+            let date = event_gregorian_date(event.title, GMT).unwrap();
+            assert_eq!(jdn_to_gregorian(event.jdn), Ok(date), "{}", event.title);
+        }
+    }
+
+    #[test]
+    fn test_historical_event_creation_date() {
+        assert_eq!(
+            historical_event(gregorian_to_jdn(-3113, 8, 11)),
+            Some("🌎 The Maya creation date (0.0.0.0.0)")
+        );
+        assert_eq!(
+            historical_event(gregorian_to_jdn(2012, 12, 21)),
+            Some("🌅 Completion of the 13th Bak'tun")
+        );
+    }
+}
@@ -0,0 +1,100 @@
+//! Maya calendar arithmetic: the Long Count, Tzolk'in and Haab' cycles,
+//! correlation constants, and the astronomical cycles tracked alongside them.
+
+pub mod astronomical;
+pub mod batch;
+pub mod calendar;
+pub mod config;
+pub mod correlation;
+pub mod date_utils;
+pub mod glyphs;
+pub mod history;
+pub mod long_count;
+pub mod mayan_date;
+pub mod metrics;
+pub mod names;
+pub mod report;
+
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone};
+
+use crate::date_utils::{checked_gregorian, gregorian_to_jdn, haab_date, tzolkin_date, DateError, HaabDate, TzolkinDate};
+use crate::long_count::LongCount;
+use crate::mayan_date::MayanDate;
+
+/// The Long Count, Tzolk'in and Haab' of a proleptic Gregorian date.
+/// `correlation` is the JDN of 0.0.0.0.0, e.g. 584283 for GMT. Dates that
+/// don't exist are rejected rather than rolled over.
+///
+/// ```
+/// use mayan_calendar::gregorian_to_mayan;
+///
+/// let (long_count, tzolkin, haab) = gregorian_to_mayan(2012, 12, 21, 584283).unwrap();
+/// assert_eq!(long_count.to_string(), "13.0.0.0.0");
+/// assert_eq!((tzolkin.number, tzolkin.yucatec_name.as_str()), (4, "Ajaw"));
+/// assert_eq!((haab.day, haab.yucatec_month.as_str()), (3, "K'ank'in"));
+///
+/// assert!(gregorian_to_mayan(0, 13, 1, 584283).is_err());
+/// assert!(gregorian_to_mayan(2023, 2, 29, 584283).is_err());
+/// ```
+pub fn gregorian_to_mayan(
+    year: i32,
+    month: i32,
+    day: i32,
+    correlation: i32,
+) -> Result<(LongCount, TzolkinDate, HaabDate), DateError> {
+    checked_gregorian(year, month, day)?;
+    let days = gregorian_to_jdn(year, month, day) - correlation;
+    Ok((LongCount::from_days(days), tzolkin_date(days), haab_date(days)))
+}
+
+/// The proleptic Gregorian date a Long Count falls on under `correlation`, or
+/// an error for counts past the years chrono can represent.
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use mayan_calendar::{long_count::LongCount, mayan_to_gregorian};
+///
+/// let pakal_birth: LongCount = "9.8.9.13.0".parse().unwrap();
+/// assert_eq!(mayan_to_gregorian(&pakal_birth, 584283), Ok(NaiveDate::from_ymd_opt(603, 3, 24).unwrap()));
+///
+/// let far_future: LongCount = "2.0.0.0.0.0.0".parse().unwrap();
+/// assert!(mayan_to_gregorian(&far_future, 584283).is_err());
+/// ```
+pub fn mayan_to_gregorian(long_count: &LongCount, correlation: i32) -> Result<NaiveDate, DateError> {
+    long_count.to_gregorian(correlation)
+}
+
+/// Every Maya cycle position for a Julian Day Number, with no Gregorian step.
+/// `correlation` is the JDN of 0.0.0.0.0.
+///
+/// ```
+/// use mayan_calendar::maya_from_jdn;
+///
+/// let creation = maya_from_jdn(584283, 584283);
+/// assert_eq!(creation.long_count.to_string(), "0.0.0.0.0");
+/// assert_eq!(creation.tzolkin.to_string(), "4 Ajaw");
+/// assert_eq!(creation.haab.to_string(), "8 Kumk'u");
+/// ```
+pub fn maya_from_jdn(jdn: i32, correlation: i32) -> MayanDate {
+    MayanDate::from_days(jdn - correlation)
+}
+
+/// Every Maya cycle position for the civil date `datetime` falls on in its own
+/// time zone, so an evening in Mexico City stays on that day rather than
+/// rolling over at UTC midnight. Works with `Utc`, `Local` and `FixedOffset`.
+///
+/// ```
+/// use chrono::{FixedOffset, TimeZone, Utc};
+/// use mayan_calendar::maya_from_datetime;
+///
+/// let utc = Utc.with_ymd_and_hms(2012, 12, 21, 3, 0, 0).unwrap();
+/// assert_eq!(maya_from_datetime(&utc, 584283).long_count.to_string(), "13.0.0.0.0");
+///
+/// // The same instant is still the evening of the 20th in Mexico City
+/// let mexico_city = utc.with_timezone(&FixedOffset::west_opt(6 * 3600).unwrap());
+/// assert_eq!(maya_from_datetime(&mexico_city, 584283).long_count.to_string(), "12.19.19.17.19");
+/// ```
+pub fn maya_from_datetime<Tz: TimeZone>(datetime: &DateTime<Tz>, correlation: i32) -> MayanDate {
+    let date = datetime.date_naive();
+    maya_from_jdn(gregorian_to_jdn(date.year(), date.month() as i32, date.day() as i32), correlation)
+}
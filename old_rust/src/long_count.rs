@@ -0,0 +1,518 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+use chrono::NaiveDate;
+
+use crate::correlation::days_to_gregorian;
+use crate::date_utils::DateError;
+
+// Long Count Parse Error Handling
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ParseError {
+    #[error("Expected 5 to 8 dot-separated fields, found {0}")]
+    WrongFieldCount(usize),
+    #[error("Invalid number in Long Count: {0:?}")]
+    InvalidNumber(String),
+    #[error("{unit} value {value} out of range (0-{max})")]
+    OutOfRange { unit: &'static str, value: i32, max: i32 },
+    #[error("Long Count {0} is too far from 0.0.0.0.0 to count in days")]
+    TooLarge(String),
+}
+
+// Long Count period lengths in days
+pub const KINCHILTUN_DAYS: i32 = 1_152_000_000; // A K'inchiltun is 20 Kalabtuns
+pub const KALABTUN_DAYS: i32 = 57_600_000;      // A Kalabtun is 20 Piktuns
+pub const PIKTUN_DAYS: i32 = 2_880_000;         // A Piktun is 20 Baktuns
+pub const BAKTUN_DAYS: i32 = 144_000;           // A Baktun is 20 Katuns
+pub const KATUN_DAYS: i32 = 7_200;              // A Katun is 20 Tuns
+pub const TUN_DAYS: i32 = 360;                  // A Tun is 18 Uinals
+pub const UINAL_DAYS: i32 = 20;                 // An Uinal is 20 Kins
+
+/// Length of one creation cycle ("world age") of 13 baktuns
+pub const CREATION_CYCLE_DAYS: i32 = 13 * BAKTUN_DAYS;
+
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LongCount {
+    // Higher orders stay zero until the count passes 20 baktuns
+    pub kinchiltun: i32,
+    pub kalabtun: i32,
+    pub piktun: i32,
+    pub baktun: i32,
+    pub katun: i32,
+    pub tun: i32,
+    pub uinal: i32,
+    pub kin: i32,
+}
+
+impl LongCount {
+    /// Creates a standard five-place Long Count. Positions past their range carry
+    /// upward, so 0.0.0.18.0 comes out as 0.0.1.0.0.
+    pub const fn new(baktun: i32, katun: i32, tun: i32, uinal: i32, kin: i32) -> Self {
+        Self::from_days(
+            baktun * BAKTUN_DAYS + katun * KATUN_DAYS + tun * TUN_DAYS + uinal * UINAL_DAYS + kin,
+        )
+    }
+
+    pub const fn from_days(days: i32) -> Self {
+        let kinchiltun = days / KINCHILTUN_DAYS;
+        let rem = days % KINCHILTUN_DAYS;
+        let kalabtun = rem / KALABTUN_DAYS;
+        let rem = rem % KALABTUN_DAYS;
+        let piktun = rem / PIKTUN_DAYS;
+        let rem = rem % PIKTUN_DAYS;
+        let baktun = rem / BAKTUN_DAYS;
+        let rem1 = rem % BAKTUN_DAYS;
+        let katun = rem1 / KATUN_DAYS;
+        let rem2 = rem1 % KATUN_DAYS;
+        let tun = rem2 / TUN_DAYS;
+        let rem3 = rem2 % TUN_DAYS;
+        let uinal = rem3 / UINAL_DAYS;
+        let kin = rem3 % UINAL_DAYS;
+        Self { kinchiltun, kalabtun, piktun, baktun, katun, tun, uinal, kin }
+    }
+
+    pub fn to_days(&self) -> i32 {
+        self.kinchiltun * KINCHILTUN_DAYS +
+        self.kalabtun * KALABTUN_DAYS +
+        self.piktun * PIKTUN_DAYS +
+        self.baktun * BAKTUN_DAYS +
+        self.katun * KATUN_DAYS +
+        self.tun * TUN_DAYS +
+        self.uinal * UINAL_DAYS +
+        self.kin
+    }
+
+    /// Moves the date by `n` days, carrying through the 20-kin uinal and 18-uinal tun
+    pub fn add_days(&self, n: i32) -> Self {
+        Self::from_days(self.to_days() + n)
+    }
+
+    /// Converts the Long Count to a Gregorian date; `correlation` is the JDN of
+    /// 0.0.0.0.0. Counts of a k'inchiltun or so fall past the years chrono
+    /// can represent and give `DateError::JulianDay`.
+    pub fn to_gregorian(&self, correlation: i32) -> Result<NaiveDate, DateError> {
+        days_to_gregorian(self.to_days(), correlation)
+    }
+
+    /// Dotted form with at least `precision` places (5 to 8), padding the
+    /// higher orders with zeros, e.g. 7 gives "0.0.13.0.0.0.0" for 13.0.0.0.0.
+    /// Non-zero higher orders are always shown, as with `Display`. Dates before
+    /// 0.0.0.0.0 get a leading '-', e.g. "-0.0.0.0.1" for the day before.
+    pub fn to_string_with_precision(&self, precision: usize) -> String {
+        // Before creation every place is negative; show the sign once, up front
+        let negative = self.positions().iter().any(|&p| p < 0);
+        let positions = self.positions().map(i32::abs);
+        let shortest = 8 - precision.clamp(5, 8);
+        let first = positions[..shortest].iter().position(|&p| p != 0).unwrap_or(shortest);
+        let parts: Vec<String> = positions[first..].iter().map(|p| p.to_string()).collect();
+        format!("{}{}", if negative { "-" } else { "" }, parts.join("."))
+    }
+
+    /// All eight positions, highest order first
+    fn positions(&self) -> [i32; 8] {
+        [
+            self.kinchiltun,
+            self.kalabtun,
+            self.piktun,
+            self.baktun,
+            self.katun,
+            self.tun,
+            self.uinal,
+            self.kin,
+        ]
+    }
+}
+
+// Distance numbers: inscriptions count forward and back from a base date
+impl Add for LongCount {
+    type Output = LongCount;
+
+    fn add(self, distance: LongCount) -> LongCount {
+        self.add_days(distance.to_days())
+    }
+}
+
+impl Sub for LongCount {
+    type Output = LongCount;
+
+    fn sub(self, distance: LongCount) -> LongCount {
+        self.add_days(-distance.to_days())
+    }
+}
+
+// Chronological order; positions only break ties between un-normalized values
+impl Ord for LongCount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_days()
+            .cmp(&other.to_days())
+            .then_with(|| self.positions().cmp(&other.positions()))
+    }
+}
+
+impl PartialOrd for LongCount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Days from `a` to `b`; negative when `b` is the earlier date
+pub fn days_between(a: &LongCount, b: &LongCount) -> i32 {
+    b.to_days() - a.to_days()
+}
+
+/// The distance number separating two dates, whichever comes first
+pub fn distance(a: &LongCount, b: &LongCount) -> LongCount {
+    LongCount::from_days(days_between(a, b).abs())
+}
+
+/// How many 13-baktun creation cycles have elapsed since 0.0.0.0.0; the
+/// current one began at 13.0.0.0.0 on 21 December 2012 (GMT)
+pub fn creation_cycle(days_since_creation: i32) -> i32 {
+    days_since_creation.div_euclid(CREATION_CYCLE_DAYS)
+}
+
+/// The date as its cycle and the Long Count within it, e.g. "Cycle 1, 0.0.13.17.4"
+pub fn cycle_string(days_since_creation: i32) -> String {
+    let within = LongCount::from_days(days_since_creation.rem_euclid(CREATION_CYCLE_DAYS));
+    format!("Cycle {}, {}", creation_cycle(days_since_creation), within)
+}
+
+// The first multiple of `period` days after `from_days`, and how far off it is
+fn next_ending(from_days: i32, period: i32) -> (LongCount, i32) {
+    let ending = (from_days.div_euclid(period) + 1) * period;
+    (LongCount::from_days(ending), ending - from_days)
+}
+
+/// The first k'atun ending (tun, uinal and kin all zero) after `from_days`,
+/// with the days until it
+pub fn next_period_ending(from_days: i32) -> (LongCount, i32) {
+    next_ending(from_days, KATUN_DAYS)
+}
+
+/// The first b'ak'tun ending after `from_days`, with the days until it: the
+/// countdown that ran to 13.0.0.0.0 in 2012 and now runs to 14.0.0.0.0
+pub fn next_baktun_ending(from_days: i32) -> (LongCount, i32) {
+    next_ending(from_days, BAKTUN_DAYS)
+}
+
+impl FromStr for LongCount {
+    type Err = ParseError;
+
+    /// Parses a dotted Long Count such as "9.17.0.0.0", or up to three extra
+    /// leading positions for piktun, kalabtun and k'inchiltun ("1.0.0.0.0.8").
+    /// A leading '-' counts back from 0.0.0.0.0, as `Display` writes it.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (sign, digits) = match s.trim().strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, s.trim()),
+        };
+        let fields: Vec<&str> = digits.split('.').collect();
+        if !(5..=8).contains(&fields.len()) {
+            return Err(ParseError::WrongFieldCount(fields.len()));
+        }
+        // Every position is base 20 except the uinal, which carries into the tun at 18
+        let limits = [
+            ("kinchiltun", 19),
+            ("kalabtun", 19),
+            ("piktun", 19),
+            ("baktun", 19),
+            ("katun", 19),
+            ("tun", 19),
+            ("uinal", 17),
+            ("kin", 19),
+        ];
+        // Missing high orders are zero, so align the fields to the right
+        let skip = limits.len() - fields.len();
+        let mut values = [0; 8];
+        for (i, (field, (unit, max))) in fields.iter().zip(&limits[skip..]).enumerate() {
+            let value: i32 = field
+                .trim()
+                .parse()
+                .map_err(|_| ParseError::InvalidNumber(field.to_string()))?;
+            if !(0..=*max).contains(&value) {
+                return Err(ParseError::OutOfRange { unit, value, max: *max });
+            }
+            values[skip + i] = value;
+        }
+        // Each place fits its radix, but eight of them can still pass the i32 range of days
+        let periods = [KINCHILTUN_DAYS, KALABTUN_DAYS, PIKTUN_DAYS, BAKTUN_DAYS, KATUN_DAYS, TUN_DAYS, UINAL_DAYS, 1];
+        let days: i64 = values.iter().zip(periods).map(|(&v, p)| sign * v as i64 * p as i64).sum();
+        if i32::try_from(days).is_err() {
+            return Err(ParseError::TooLarge(s.trim().to_string()));
+        }
+        let [kinchiltun, kalabtun, piktun, baktun, katun, tun, uinal, kin] = values.map(|v| sign as i32 * v);
+        Ok(Self { kinchiltun, kalabtun, piktun, baktun, katun, tun, uinal, kin })
+    }
+}
+
+impl fmt::Display for LongCount {
+    /// Shows the familiar five places, plus any non-zero higher orders
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_with_precision(5))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::correlation::Correlation;
+    use crate::date_utils::{haab_date, tzolkin_date};
+    use chrono::Datelike;
+
+    #[test]
+    fn test_long_count_to_gregorian() {
+        let gmt = Correlation::GMT.jdn_offset();
+
+        // Creation date: 0.0.0.0.0 = August 11, 3114 BCE (astronomical year -3113)
+        let creation = LongCount::from_days(0);
+        assert_eq!(creation.to_gregorian(gmt), Ok(NaiveDate::from_ymd_opt(-3113, 8, 11).unwrap()));
+
+        // End of the 13th baktun: 13.0.0.0.0 = December 21, 2012
+        let baktun_13 = LongCount::new(13, 0, 0, 0, 0);
+        assert_eq!(baktun_13.to_gregorian(gmt), Ok(NaiveDate::from_ymd_opt(2012, 12, 21).unwrap()));
+
+        // The +2 correlation shifts the same Long Count two days later
+        let plus2 = Correlation::GMTPlus2.jdn_offset();
+        assert_eq!(baktun_13.to_gregorian(plus2), Ok(NaiveDate::from_ymd_opt(2012, 12, 23).unwrap()));
+
+        // Parseable, but beyond chrono's calendar
+        for s in ["2.0.0.0.0.0.0", "1.0.0.0.0.0.0.0"] {
+            let lc: LongCount = s.parse().unwrap();
+            assert_eq!(lc.to_gregorian(gmt), Err(DateError::JulianDay(lc.to_days() as i64 + gmt as i64)), "{}", s);
+        }
+        assert!(LongCount::from_days(i32::MAX).to_gregorian(gmt).is_err());
+    }
+
+    #[test]
+    fn test_long_count_parse_round_trip() {
+        let lc: LongCount = "9.17.0.0.0".parse().unwrap();
+        assert_eq!(lc, LongCount::new(9, 17, 0, 0, 0));
+        assert_eq!(lc.to_string(), "9.17.0.0.0");
+
+        for days in [0, 1, 359, 360, 1_366_560, 1_872_000] {
+            let lc = LongCount::from_days(days);
+            assert_eq!(lc.to_string().parse::<LongCount>(), Ok(lc));
+        }
+    }
+
+    #[test]
+    fn test_long_count_parse_errors() {
+        assert_eq!("9.17.0.0".parse::<LongCount>(), Err(ParseError::WrongFieldCount(4)));
+        assert_eq!(
+            "1.1.1.9.17.0.0.0.0".parse::<LongCount>(),
+            Err(ParseError::WrongFieldCount(9))
+        );
+        assert_eq!(
+            "9.17.x.0.0".parse::<LongCount>(),
+            Err(ParseError::InvalidNumber("x".to_string()))
+        );
+        assert_eq!(
+            "9.17.0.18.0".parse::<LongCount>(),
+            Err(ParseError::OutOfRange { unit: "uinal", value: 18, max: 17 })
+        );
+        assert_eq!(
+            "9.17.0.0.20".parse::<LongCount>(),
+            Err(ParseError::OutOfRange { unit: "kin", value: 20, max: 19 })
+        );
+        assert!("9.-1.0.0.0".parse::<LongCount>().is_err());
+
+        // Every place in range, but more days than an i32 holds
+        assert_eq!(
+            "19.19.19.19.19.19.17.19".parse::<LongCount>(),
+            Err(ParseError::TooLarge("19.19.19.19.19.19.17.19".to_string()))
+        );
+        assert!("2.0.0.0.0.0.0.0".parse::<LongCount>().is_err());
+        let longest = LongCount::from_days(i32::MAX);
+        assert_eq!(longest.to_string().parse::<LongCount>(), Ok(longest));
+    }
+
+    #[test]
+    fn test_long_count_before_creation() {
+        // The sign goes on the top place only, and the string parses back
+        assert_eq!(LongCount::from_days(-1).to_string(), "-0.0.0.0.1");
+        assert_eq!(LongCount::from_days(-BAKTUN_DAYS - 21).to_string(), "-1.0.0.1.1");
+        assert_eq!(LongCount::from_days(-1).to_string_with_precision(7), "-0.0.0.0.0.0.1");
+        assert_eq!(" -13.0.0.0.0".parse::<LongCount>(), Ok(LongCount::from_days(-13 * BAKTUN_DAYS)));
+        assert_eq!("-0.0.0.0.0".parse::<LongCount>(), Ok(LongCount::from_days(0)));
+        for days in (-3_000_000..0).step_by(997).chain([i32::MIN, i32::MIN + 1]) {
+            let lc = LongCount::from_days(days);
+            assert_eq!(lc.to_string().parse::<LongCount>(), Ok(lc), "{}", lc);
+        }
+        // Further back than an i32 holds
+        assert_eq!(
+            "-19.19.19.19.19.19.17.19".parse::<LongCount>(),
+            Err(ParseError::TooLarge("-19.19.19.19.19.19.17.19".to_string()))
+        );
+        // Only the top place carries the sign
+        assert!("-9.-1.0.0.0".parse::<LongCount>().is_err());
+    }
+
+    #[test]
+    fn test_long_count_uinal_carry() {
+        // A tun holds 18 uinals, so the uinal runs 0-17 and carries at 18
+        assert_eq!(LongCount::from_days(359).to_string(), "0.0.0.17.19");
+        assert_eq!(LongCount::from_days(360).to_string(), "0.0.1.0.0");
+        assert_eq!(LongCount::new(0, 0, 0, 18, 0).to_string(), "0.0.1.0.0");
+        for days in (0..2_000_000).step_by(7) {
+            let lc = LongCount::from_days(days);
+            assert!((0..18).contains(&lc.uinal), "{}", lc);
+            assert_eq!(lc.to_days(), days);
+        }
+    }
+
+    #[test]
+    fn test_long_count_bijective() {
+        // Every day count maps to one Long Count and back, before creation too
+        for days in (-3_000_000..3_000_000).step_by(997).chain(-400..400) {
+            let lc = LongCount::from_days(days);
+            assert_eq!(lc.to_days(), days);
+            assert_eq!(LongCount::from_days(lc.to_days()), lc);
+        }
+        // and distinct days never share one
+        let longest = LongCount::from_days(i32::MAX);
+        assert_eq!(longest.to_days(), i32::MAX);
+        assert_ne!(LongCount::from_days(1), LongCount::from_days(-1));
+    }
+
+    #[test]
+    fn test_long_count_distance_numbers() {
+        let lc = |s: &str| s.parse::<LongCount>().unwrap();
+        assert_eq!(lc("9.0.0.0.0") + lc("0.0.1.0.0"), lc("9.0.1.0.0"));
+        // 18 uinals make a tun, 20 tuns a katun
+        assert_eq!(lc("9.0.0.17.0") + lc("0.0.0.1.0"), lc("9.0.1.0.0"));
+        assert_eq!(lc("9.0.19.17.19") + lc("0.0.0.0.1"), lc("9.1.0.0.0"));
+        assert_eq!(lc("9.17.0.0.0") - lc("0.0.0.0.1"), lc("9.16.19.17.19"));
+        assert_eq!(lc("9.12.11.5.18") + lc("0.4.5.6.7") - lc("0.4.5.6.7"), lc("9.12.11.5.18"));
+        assert_eq!(lc("12.19.19.17.19").add_days(1), lc("13.0.0.0.0"));
+        assert_eq!(lc("13.0.0.0.0").add_days(-1), lc("12.19.19.17.19"));
+    }
+
+    #[test]
+    fn test_long_count_ordering_and_distance() {
+        let lc = |s: &str| s.parse::<LongCount>().unwrap();
+        let mut dates = vec![lc("13.0.0.0.0"), lc("9.12.11.5.18"), lc("1.0.0.0.0.8"), lc("9.8.9.13.0")];
+        dates.sort();
+        assert_eq!(dates, vec![lc("9.8.9.13.0"), lc("9.12.11.5.18"), lc("13.0.0.0.0"), lc("1.0.0.0.0.8")]);
+        assert!(lc("9.17.0.0.0") > lc("9.16.19.17.19"));
+        assert!(LongCount::from_days(-1) < LongCount::from_days(0));
+
+        // Pakal lived 80 years, from 9.8.9.13.0 to 9.12.11.5.18
+        let (birth, death) = (lc("9.8.9.13.0"), lc("9.12.11.5.18"));
+        assert_eq!(days_between(&birth, &death), 29_378);
+        assert_eq!(days_between(&death, &birth), -29_378);
+        assert_eq!(distance(&birth, &death), lc("0.4.1.10.18"));
+        assert_eq!(distance(&death, &birth), distance(&birth, &death));
+        assert_eq!(birth + distance(&birth, &death), death);
+        assert_eq!(distance(&birth, &birth), LongCount::from_days(0));
+    }
+
+    #[test]
+    fn test_long_count_higher_orders() {
+        // Tortuguero Monument 6: 13.0.0.0.0 4 Ajaw 3 K'ank'in stays a five-place date
+        let baktun_13 = LongCount::from_days(1_872_000);
+        assert_eq!(baktun_13.to_string(), "13.0.0.0.0");
+        assert_eq!(tzolkin_date(baktun_13.to_days()).yucatec_name, "Ajaw");
+        assert_eq!(haab_date(baktun_13.to_days()).day, 3);
+        assert_eq!(haab_date(baktun_13.to_days()).yucatec_month, "K'ank'in");
+
+        // Palenque Temple of the Inscriptions: 1.0.0.0.0.8 5 Lamat 1 Mol, in 4772 CE
+        let future: LongCount = "1.0.0.0.0.8".parse().unwrap();
+        assert_eq!(future.piktun, 1);
+        assert_eq!(future.baktun, 0);
+        assert_eq!(future.to_days(), PIKTUN_DAYS + 8);
+        assert_eq!(LongCount::from_days(PIKTUN_DAYS + 8), future);
+        assert_eq!(future.to_string(), "1.0.0.0.0.8");
+        assert_eq!(tzolkin_date(future.to_days()).number, 5);
+        assert_eq!(tzolkin_date(future.to_days()).yucatec_name, "Lamat");
+        assert_eq!(haab_date(future.to_days()).day, 1);
+        assert_eq!(haab_date(future.to_days()).yucatec_month, "Mol");
+        assert_eq!(future.to_gregorian(584283).unwrap().year(), 4772);
+
+        // Seven- and eight-place counts round trip through the string form
+        for days in [KALABTUN_DAYS + 3 * PIKTUN_DAYS + 17, KINCHILTUN_DAYS + 1] {
+            let lc = LongCount::from_days(days);
+            assert_eq!(lc.to_string().parse::<LongCount>(), Ok(lc));
+            assert_eq!(lc.to_days(), days);
+        }
+        assert_eq!(LongCount::from_days(KINCHILTUN_DAYS).to_string(), "1.0.0.0.0.0.0.0");
+    }
+
+    #[test]
+    fn test_creation_cycle() {
+        // 13.0.0.0.0 on 21 December 2012 closes cycle 0 and opens cycle 1
+        let baktun_13 = 1_872_000;
+        assert_eq!(LongCount::from_days(baktun_13).to_gregorian(584283), Ok(NaiveDate::from_ymd_opt(2012, 12, 21).unwrap()));
+        assert_eq!(creation_cycle(baktun_13 - 1), 0);
+        assert_eq!(creation_cycle(baktun_13), 1);
+        assert_eq!(cycle_string(baktun_13 - 1), "Cycle 0, 12.19.19.17.19");
+        assert_eq!(cycle_string(baktun_13), "Cycle 1, 0.0.0.0.0");
+        assert_eq!(cycle_string(baktun_13 + LongCount::new(0, 2, 4, 1, 0).to_days()), "Cycle 1, 0.2.4.1.0");
+        assert_eq!(cycle_string(2 * CREATION_CYCLE_DAYS + 5), "Cycle 2, 0.0.0.0.5");
+        assert_eq!(creation_cycle(-1), -1);
+    }
+
+    #[test]
+    fn test_next_period_ending() {
+        // Pakal died on 9.12.11.5.18, eight years before the 9.13.0.0.0 k'atun ending of 692
+        let pakal_death = LongCount::new(9, 12, 11, 5, 18).to_days();
+        let (ending, days) = next_period_ending(pakal_death);
+        assert_eq!(ending, LongCount::new(9, 13, 0, 0, 0));
+        assert_eq!(days, 3_122);
+        assert_eq!(ending.to_gregorian(584283), Ok(NaiveDate::from_ymd_opt(692, 3, 16).unwrap()));
+
+        // A k'atun ending itself looks ahead to the one after
+        let katun_10 = LongCount::new(9, 10, 0, 0, 0).to_days();
+        assert_eq!(next_period_ending(katun_10), (LongCount::new(9, 11, 0, 0, 0), KATUN_DAYS));
+        assert_eq!(next_period_ending(katun_10 - 1), (LongCount::new(9, 10, 0, 0, 0), 1));
+
+        assert_eq!(next_baktun_ending(pakal_death).0, LongCount::new(10, 0, 0, 0, 0));
+        assert_eq!(next_baktun_ending(1_872_000), (LongCount::new(14, 0, 0, 0, 0), BAKTUN_DAYS));
+        assert_eq!(next_baktun_ending(-1), (LongCount::from_days(0), 1));
+        // Counting down to 21 December 2012, then starting over
+        assert_eq!(next_baktun_ending(1_872_000 - 1), (LongCount::new(13, 0, 0, 0, 0), 1));
+        assert_eq!(next_baktun_ending(1_872_000 - 365), (LongCount::new(13, 0, 0, 0, 0), 365));
+        assert_eq!(next_baktun_ending(1_872_000 + 1), (LongCount::new(14, 0, 0, 0, 0), BAKTUN_DAYS - 1));
+    }
+
+    #[test]
+    fn test_to_string_with_precision() {
+        let end = LongCount::new(13, 0, 0, 0, 0);
+        assert_eq!(end.to_string_with_precision(5), "13.0.0.0.0");
+        assert_eq!(end.to_string_with_precision(5), end.to_string());
+        assert_eq!(end.to_string_with_precision(6), "0.13.0.0.0.0");
+        assert_eq!(end.to_string_with_precision(7), "0.0.13.0.0.0.0");
+        assert_eq!(end.to_string_with_precision(8), "0.0.0.13.0.0.0.0");
+        // Out-of-range precisions are clamped to 5-8
+        assert_eq!(end.to_string_with_precision(0), "13.0.0.0.0");
+        assert_eq!(end.to_string_with_precision(12), "0.0.0.13.0.0.0.0");
+
+        // A non-zero piktun is never dropped, however few places are asked for
+        let piktun = LongCount::from_days(PIKTUN_DAYS + 1);
+        assert_eq!(piktun.to_string_with_precision(5), "1.0.0.0.0.1");
+        assert_eq!(piktun.to_string_with_precision(7), "0.1.0.0.0.0.1");
+        assert_eq!(piktun.to_string_with_precision(7).parse::<LongCount>(), Ok(piktun));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_from_days_in_range(days in proptest::num::i32::ANY) {
+            let lc = LongCount::from_days(days);
+            proptest::prop_assert_eq!(lc.to_days(), days);
+            // Every place stays under its radix and shares the sign of the count
+            let places = [lc.kinchiltun, lc.kalabtun, lc.piktun, lc.baktun, lc.katun, lc.tun, lc.uinal, lc.kin];
+            let radices = [i32::MAX, 20, 20, 20, 20, 20, 18, 20];
+            for (place, radix) in places.into_iter().zip(radices) {
+                proptest::prop_assert!(place.abs() < radix);
+                proptest::prop_assert!(place == 0 || place.signum() == days.signum());
+            }
+        }
+
+        #[test]
+        fn prop_display_round_trips(days in proptest::num::i32::ANY) {
+            let lc = LongCount::from_days(days);
+            proptest::prop_assert_eq!(lc.to_string().parse::<LongCount>(), Ok(lc));
+        }
+    }
+}
@@ -1,695 +1,328 @@
-use chrono::{Datelike, NaiveDate, Utc};  // Added NaiveDate
-use eframe::egui::{CentralPanel, ColorImage, Context, TextureOptions, Ui};
-use eframe::App;
-use std::collections::HashMap;
-use eframe::egui;
-
-/// Convert a Gregorian date to Julian Day Number (JDN)
-fn gregorian_to_jdn(year: i32, month: i32, day: i32) -> i32 {
-    let a = (14 - month) / 12;
-    let y = year + 4800 - a;
-    let m = month + 12 * a - 3;
-    day + ((153 * m + 2) / 5) + 365 * y + y / 4 - y / 100 + y / 400 - 32045
+use std::fs::File;
+use std::io::{BufReader, BufWriter, IsTerminal, Write};
+use std::path::PathBuf;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+use tracing::Level;
+use tracing_subscriber::EnvFilter;
+
+use mayan_calendar::batch::convert_csv;
+use mayan_calendar::config::Config;
+use mayan_calendar::date_utils::{gregorian_to_jdn, tzolkin_date, CalendarSystem, Timezone};
+use mayan_calendar::long_count::LongCount;
+use mayan_calendar::report::{text_report_with, tzolkin_table_text, ReportFormat, ReportOptions};
+
+#[cfg(feature = "gui")]
+mod gui;
+
+// ----- COMMAND LINE -----
+
+const USAGE: &str = "Usage: mayan_calendar [--date YYYY-MM-DD | --longcount B.K.T.U.K | --watch] \
+                     [--calendar gregorian|julian|historical] [--format plain|markdown|json] \
+                     [--color auto|always|never] [--tzolkin-table]\n       \
+                     mayan_calendar --csv INPUT.csv [--out OUTPUT.csv] [--calendar gregorian|julian|historical]";
+
+// When to color the plain report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum ColorChoice {
+    // Only on a terminal, and only if NO_COLOR is unset
+    #[default]
+    Auto,
+    Always,
+    Never,
 }
 
-//// Convert a number (0-19) to a Mayan numeral Unicode character
-fn mayan_numeral(n: i32) -> char {
-    match n {
-        0..=19 => char::from_u32(0x1D2E0 + n as u32).unwrap(),
-        _ => '❓', // If out of range, return a placeholder
+impl ColorChoice {
+    fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
     }
 }
 
-
-//// Get Tzolk’in Day Glyphs
-fn tzolkin_glyphs() -> HashMap<&'static str, &'static str> {
-  let mut glyphs = HashMap::new();
-  let tzolkin_days = [
-      "Imix", "Ik'", "Ak'b'al", "K'an", "Chikchan",
-      "Kimi", "Manik'", "Lamat", "Muluk", "Ok",
-      "Chuwen", "Eb'", "B'en", "Ix", "Men",
-      "Kib'", "Kab'an", "Etz'nab'", "Kawak", "Ajaw"
-  ];
-  let tzolkin_symbols = [
-      "🐊", "🌬️", "🌑", "🌽", "🐍",
-      "💀", "🖐️", "🌟", "💧", "🐶",
-      "🕷️", "🌾", "🌳", "🦉", "🦅",
-      "🐝", "🌀", "🔪", "⛈️", "👑"
-  ];
-  for i in 0..20 {
-      glyphs.insert(tzolkin_days[i], tzolkin_symbols[i]);
-  }
-  glyphs
-}
-
-//// Get Haab’ Month Glyphs
-fn haab_glyphs() -> HashMap<&'static str, &'static str> {
-  let mut glyphs = HashMap::new();
-  let haab_months = [
-      "Pop", "Wo'", "Sip", "Sotz'", "Sek", "Xul", "Yaxkin", "Mol",
-      "Ch'en", "Yax", "Zac", "Ceh", "Mac", "Kankin", "Muan", "Pax",
-      "Kayab", "Kumk'u", "Wayeb'"
-  ];
-  let haab_symbols = [
-      "📜", "🌊", "🔥", "🦇", "🌱", "💨", "🌞", "🌧️",
-      "🏺", "🌿", "❄️", "🐆", "🎭", "🔥", "🦜", "🎵",
-      "🐢", "🌰", "⚠️"
-  ];
-  for i in 0..19 {
-      glyphs.insert(haab_months[i], haab_symbols[i]);
-  }
-  glyphs
-}
-
-fn long_count(days: i32) -> (i32, i32, i32, i32, i32) {
-    let baktun = days / 144_000;
-    let rem1 = days % 144_000;
-    let katun = rem1 / 7_200;
-    let rem2 = rem1 % 7_200;
-    let tun = rem2 / 360;
-    let rem3 = rem2 % 360;
-    let uinal = rem3 / 20;
-    let kin = rem3 % 20;
-    (baktun, katun, tun, uinal, kin)
+// What to print; with no arguments at all the GUI opens instead
+#[derive(Debug, Default, PartialEq)]
+struct ReportRequest {
+    date: Option<NaiveDate>,
+    format: ReportFormat,
+    color: ColorChoice,
+    // Redraw today's report in place until interrupted
+    watch: bool,
+    // Print the 260-day grid with the date marked instead of the report
+    tzolkin_table: bool,
+    // Batch-convert the first column of a CSV, writing to `out` or stdout
+    csv: Option<PathBuf>,
+    out: Option<PathBuf>,
+    // The calendar --date and CSV dates are written in
+    calendar: CalendarSystem,
 }
 
-/// Generate an ASCII-art Mayan Long Count representation
-fn mayan_ascii_number(n: i32) -> String {
-    let mut result = String::new();
-
-    // Calculate the number of bars and dots
-    let bars = n / 5;
-    let dots = n % 5;
-
-    // Add bars (one per line)
-    for _ in 0..bars {
-        result.push_str("▬▬▬▬▬▬\n"); // Full-width bar
+// Resolve the report requested on the command line; None means "today in the GUI"
+fn parse_args(args: &[String], correlation: i32) -> Result<Option<ReportRequest>, String> {
+    if args.is_empty() {
+        return Ok(None);
     }
-
-    // Add dots (on a single line after bars)
-    if dots > 0 {
-        for _ in 0..dots {
-            result.push('●'); // Add a dot
+    let mut request = ReportRequest::default();
+    // --calendar may come after --date, so the date is read once all flags are in
+    let mut date_text = None;
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if flag == "--watch" {
+            request.watch = true;
+            continue;
+        }
+        if flag == "--tzolkin-table" {
+            request.tzolkin_table = true;
+            continue;
+        }
+        let value = args.next().ok_or_else(|| USAGE.to_string())?;
+        match flag.as_str() {
+            "--date" => date_text = Some(value),
+            "--longcount" => {
+                let lc = value
+                    .parse::<LongCount>()
+                    .map_err(|e| format!("Invalid Long Count '{}': {}", value, e))?;
+                let date = lc
+                    .to_gregorian(correlation)
+                    .map_err(|e| format!("Long Count '{}' has no Gregorian date: {}", value, e))?;
+                request.date = Some(date);
+            }
+            "--format" => request.format = value.parse().map_err(|e| format!("{}", e))?,
+            "--csv" => request.csv = Some(PathBuf::from(value)),
+            "--out" => request.out = Some(PathBuf::from(value)),
+            "--color" => {
+                request.color = match value.as_str() {
+                    "auto" => ColorChoice::Auto,
+                    "always" => ColorChoice::Always,
+                    "never" => ColorChoice::Never,
+                    _ => return Err(format!("Invalid color choice '{}' (expected auto, always or never)", value)),
+                }
+            }
+            "--calendar" => {
+                request.calendar = match value.as_str() {
+                    "gregorian" => CalendarSystem::Gregorian,
+                    "julian" => CalendarSystem::Julian,
+                    "historical" => CalendarSystem::Historical,
+                    _ => {
+                        return Err(format!(
+                            "Invalid calendar '{}' (expected gregorian, julian or historical)",
+                            value
+                        ))
+                    }
+                }
+            }
+            _ => return Err(USAGE.to_string()),
         }
-        result.push('\n'); // Newline after dots
     }
-
-    // Handle zero (special Mayan zero glyph)
-    if n == 0 {
-        result.push_str("𝋠\n"); // Mayan zero glyph fallback
+    if let Some(value) = date_text {
+        let date = request
+            .calendar
+            .parse_date(value)
+            .map_err(|e| format!("Invalid date '{}': {}", value, e))?;
+        request.date = Some(date);
     }
-
-    result
-}
-
-/// Get Long Count Glyphs
-fn long_count_glyphs() -> HashMap<i32, &'static str> {
-  let mut glyphs = HashMap::new();
-  glyphs.insert(0, "🪵");  // Placeholder for Baktun glyph
-  glyphs.insert(1, "🔥");
-  glyphs.insert(2, "💧");
-  glyphs.insert(3, "🌿");
-  glyphs.insert(4, "🌞");
-  glyphs.insert(5, "🌕");
-  glyphs.insert(6, "🌎");
-  glyphs.insert(7, "🐍");
-  glyphs.insert(8, "🌪️");
-  glyphs.insert(9, "⭐");
-  glyphs.insert(10, "🔺");
-  glyphs.insert(11, "🏹");
-  glyphs.insert(12, "🌀");
-  glyphs.insert(13, "🔮");
-  glyphs
-}
-
-/// Get Historical Event Glyphs
-fn historical_glyphs() -> HashMap<&'static str, &'static str> {
-  let mut glyphs = HashMap::new();
-  glyphs.insert("🌎 The Maya creation date (0.0.0.0.0)", "🌀");
-  glyphs.insert("📜 Earliest Long Count Date Found", "📜");
-  glyphs.insert("⚔️ Teotihuacan Influence Over Tikal Begins", "⚔️");
-  glyphs.insert("🏛️ Dynasty of Copán Founded", "🏛️");
-  glyphs.insert("🛑 Tikal Defeated by Calakmul", "🛑");
-  glyphs.insert("👑 King Jasaw Chan K’awiil I Crowned in Tikal", "👑");
-  glyphs.insert("🏰 Toltec-Maya Rule in Chichen Itzá Begins", "🏰");
-  glyphs.insert("🏹 Spanish Conquer the Last Maya City, Tayasal", "🏹");
-  glyphs
-}
-
-// Find a historical Mayan event for the given JDN
-fn historical_event(jdn: i32) -> Option<&'static str> {
-  let events = [
-      (-3113, 8, 11, "🌎 The Maya creation date (0.0.0.0.0)"),
-      (292, 1, 1, "📜 Earliest Long Count Date Found"),
-      (378, 1, 16, "⚔️ Teotihuacan Influence Over Tikal Begins"),
-      (426, 1, 1, "🏛️ Dynasty of Copán Founded"),
-      (562, 1, 1, "🛑 Tikal Defeated by Calakmul"),
-      (682, 6, 3, "👑 King Jasaw Chan K’awiil I Crowned in Tikal"),
-      (751, 1, 1, "🏛️ Uxmal Emerges as a Major Power"),
-      (869, 12, 1, "🏛️ Tikal Abandoned"),
-      (987, 1, 1, "🏰 Toltec-Maya Rule in Chichen Itzá Begins"),
-      (1200, 1, 1, "🔺 Decline of Chichen Itzá"),
-      (1511, 8, 1, "⚔️ Spanish Make First Contact with the Maya"),
-      (1697, 3, 13, "🏹 Spanish Conquer the Last Maya City, Tayasal"),
-];
-for (e_year, e_month, e_day, desc) in events.iter() {
-  let e_jdn = gregorian_to_jdn(*e_year, *e_month, *e_day);
-  if jdn == e_jdn {
-      return Some(desc);
-  }
-}
-
-  None
-}
-
-/// Tzolk'in Calendar: Yucatec vs. K’iche’ names
-struct TzolkinDate {
-  number: i32,
-  yucatec_name: &'static str,
-  kiche_name: &'static str,
-}
-
-fn tzolkin_date(days: i32) -> TzolkinDate {
-  let number = (((days + 3) % 13 + 13) % 13) + 1;
-  let yucatec_names = [
-      "Imix", "Ik'", "Ak'b'al", "K'an", "Chikchan",
-      "Kimi", "Manik'", "Lamat", "Muluk", "Ok",
-      "Chuwen", "Eb'", "B'en", "Ix", "Men",
-      "Kib'", "Kab'an", "Etz'nab'", "Kawak", "Ajaw"
-  ];
-  let kiche_names = [
-      "Imox", "Iq'", "Aq'ab'al", "K'at", "Kan",
-      "Kame", "Kej", "Q'anil", "Tojil", "Tz'i'",
-      "B'atz'", "E", "Aj", "Ix", "Tz'ikin",
-      "Ajmaq", "No'j", "Tijax", "Kawoq", "Ajpu"
-  ];
-  let index = (((days + 19) % 20 + 20) % 20) as usize;
-  TzolkinDate {
-      number,
-      yucatec_name: yucatec_names[index],
-      kiche_name: kiche_names[index],
-  }
-}
-
-/// Haab’ Calendar: Yucatec vs. K’iche’ names
-struct HaabDate {
-  day: i32,
-  yucatec_month: &'static str,
-  kiche_month: &'static str,
-}
-
-fn haab_date(days: i32) -> HaabDate {
-  let haab_day = ((days + 348) % 365 + 365) % 365;
-  let month_index = haab_day / 20;
-  let day = haab_day % 20;
-  
-  let yucatec_months = [
-      "Pop", "Wo'", "Sip", "Sotz'", "Sek", "Xul", "Yaxkin", "Mol",
-      "Ch'en", "Yax", "Zac", "Ceh", "Mac", "Kankin", "Muan", "Pax",
-      "Kayab", "Kumk'u", "Wayeb'"
-  ];
-  
-  let kiche_months = [
-      "Pop", "Wo'", "Sip", "Zotz'", "Tzek", "Xul", "Yaxkin", "Mol",
-      "Chen", "Yax", "Zac", "Keh", "Mak", "Kank'in", "Muwan", "Pax",
-      "Kayab", "Kumk'u", "Wayeb'"
-  ];
-  
-  let month = yucatec_months[month_index as usize];
-  let kiche_month = kiche_months[month_index as usize];
-
-  HaabDate {
-      day,
-      yucatec_month: month,
-      kiche_month,
-  }
-}
-
-/// Calculate Year Bearer (Patron Tzolk’in Day of Haab’ New Year)
-fn year_bearer(jdn: i32) -> &'static str {
-  let tzolkin_days = ["Ik'", "Manik'", "Eb'", "K’an"];
-  let year_start_tzolkin_index = (((jdn + 348) % 260) % 4) as usize;
-  tzolkin_days[year_start_tzolkin_index]
-}
-
-fn moon_phase(jdn: i32) -> &'static str {
-    let synodic_month = 29.530588; // Average lunar cycle
-    let moon_age = (jdn as f64 % synodic_month) / synodic_month;
-
-    if moon_age < 0.1 {
-        "🌑 New Moon"
-    } else if moon_age < 0.25 {
-        "🌓 First Quarter"
-    } else if moon_age < 0.5 {
-        "🌕 Full Moon"
-    } else if moon_age < 0.75 {
-        "🌗 Last Quarter"
-    } else {
-        "🌑 New Moon"
+    if request.watch && request.date.is_some() {
+        return Err("--watch always follows today's date".to_string());
     }
+    if request.watch && request.tzolkin_table {
+        return Err("--tzolkin-table can't be combined with --watch".to_string());
+    }
+    if request.out.is_some() && request.csv.is_none() {
+        return Err("--out needs an --csv input".to_string());
+    }
+    Ok(Some(request))
 }
 
-/// Compute Venus Cycle Phase
-fn venus_phase(jdn: i32) -> &'static str {
-  let venus_cycle = 584; // Venus synodic period in days
-  let phase = jdn % venus_cycle;
-
-  if phase < 50 {
-      "🌟 Morning Star (Heliacal Rise)"
-  } else if phase < 215 {
-      "☀️ Superior Conjunction (Invisible)"
-  } else if phase < 265 {
-      "⭐ Evening Star (Heliacal Set)"
-  } else {
-      "🌑 Inferior Conjunction (Between Earth & Sun)"
-  }
-}
-
-/// Calculate upcoming solstices and equinoxes
-fn next_solstice_or_equinox(year: i32, month: i32, day: i32) -> (&'static str, i32) {
-  let events = [
-      ("🌸 Spring Equinox", NaiveDate::from_ymd_opt(year, 3, 20).unwrap()),
-      ("☀️ Summer Solstice", NaiveDate::from_ymd_opt(year, 6, 21).unwrap()),
-      ("🍂 Autumn Equinox", NaiveDate::from_ymd_opt(year, 9, 22).unwrap()),
-      ("❄️ Winter Solstice", NaiveDate::from_ymd_opt(year, 12, 21).unwrap()),
-  ];
+// ----- WATCH MODE -----
 
-  let today = NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap();
-  
-  for (name, date) in events.iter() {
-      if *date >= today {
-          let days_until = (*date - today).num_days() as i32;
-          return (*name, days_until);
-      }
-  }
-  
-  // If past December, return next year's Spring Equinox
-  ("🌸 Spring Equinox", 365 - (today.ordinal() - 79) as i32)
+// One screenful: clear the terminal, home the cursor, then the clock and report
+fn watch_frame(now: NaiveDateTime, report: &str) -> String {
+    format!("\x1b[2J\x1b[H🕰 Local Time: {}\n{}", now.format("%Y-%m-%d %H:%M:%S"), report)
 }
 
-/// Predict next Lunar and Solar Eclipse
-fn next_eclipse(jdn: i32) -> &'static str {
-  let saros_cycle = 6585; // Average Saros cycle in days (eclipses repeat every ~18 years)
-  let days_since_last_eclipse = jdn % saros_cycle;
-
-  if days_since_last_eclipse < 15 {
-      "🌑 Lunar Eclipse Soon!"
-  } else if days_since_last_eclipse < 30 {
-      "🌞 Solar Eclipse Soon!"
-  } else {
-      "🌘 No Eclipse Imminent"
-  }
+// Redraw every second, rebuilding the report only when the date changes
+fn watch(correlation: i32, timezone: Timezone, format: ReportFormat, options: ReportOptions) -> ! {
+    let mut date = None;
+    let mut report = String::new();
+    loop {
+        let now = timezone.now();
+        if date != Some(now.date()) {
+            date = Some(now.date());
+            report = text_report_with(now.date(), correlation, format, options);
+        }
+        let mut stdout = std::io::stdout().lock();
+        if write!(stdout, "{}", watch_frame(now, &report)).and_then(|_| stdout.flush()).is_err() {
+            // The terminal went away
+            std::process::exit(0);
+        }
+        let to_next_second = 1_000_000_000 - now.nanosecond().min(999_999_999);
+        std::thread::sleep(std::time::Duration::from_nanos(to_next_second as u64));
+    }
 }
 
-/// Check for Historical Mayan Events
-fn historical_events(year: i32, month: i32, day: i32) -> Option<&'static str> {
-  let events = [
-      (292, 1, 1, "📜 Earliest Long Count Date Found"),
-      (378, 1, 16, "🏛️ Teotihuacan Influence Over Tikal Begins"),
-      (682, 6, 3, "👑 King Jasaw Chan K’awiil I Crowned in Tikal"),
-      (869, 12, 1, "🏛️ Tikal Collapses"),
-      (1511, 8, 1, "⚔️ Spanish Make First Contact with the Maya"),
-  ];
-
-  for (e_year, e_month, e_day, desc) in events.iter() {
-      if *e_year == year && *e_month == month && *e_day == day {
-          return Some(desc);
-      }
-  }
+// ----- BATCH MODE -----
 
-  None
+fn run_batch(input: &PathBuf, out: Option<&PathBuf>, correlation: i32, calendar: CalendarSystem) -> std::io::Result<()> {
+    let input = BufReader::new(File::open(input)?);
+    let summary = match out {
+        Some(path) => convert_csv(input, BufWriter::new(File::create(path)?), correlation, calendar)?,
+        None => convert_csv(input, std::io::stdout().lock(), correlation, calendar)?,
+    };
+    eprintln!("Converted {} rows ({} with errors)", summary.rows, summary.errors);
+    Ok(())
 }
 
-// A function to map Tzolk'in names to their respective image file paths.
-fn get_tzolkin_glyphs() -> HashMap<&'static str, &'static str> {
-    let mut glyphs = HashMap::new();
-    glyphs.insert("Ajaw", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/ajaw.png");
-    glyphs.insert("Imix", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/imix.png");
-    glyphs.insert("Ik'", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/ik'.png");
-    glyphs.insert("Ak'b'al", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/ak'b'al.png");
-    glyphs.insert("K'an", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/ka'n.png");
-    glyphs.insert("Chikchan", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/chikchan.png");
-    glyphs.insert("Kimi", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/kimi.png");
-    glyphs.insert("Manik'", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/manik'.png");
-    glyphs.insert("Lamat", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/lamat.png");
-    glyphs.insert("Muluk", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/muluk.png");
-    glyphs.insert("Ok", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/ok.png");
-    glyphs.insert("Chuwen", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/chuwen.png");
-    glyphs.insert("Eb'", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/eb'.png");
-    glyphs.insert("B'en", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/b'en.png");
-    glyphs.insert("Ix", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/ix.png");
-    glyphs.insert("Men", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/men.png");
-    glyphs.insert("K'ib'", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/k'ib'.png");
-    glyphs.insert("Kab'an", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/kab'an.png");
-    glyphs.insert("Etz'nab'", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/etz'nab'.png");
-    glyphs.insert("Kawak", "C:/users/phine/documents/github/mayan_calendar/src/tzolk'in/glyphs/kawak'.png");
-    glyphs
-}
-
-// A function to load Tzolk'in names as texture from image
-fn load_tzolkin_image_as_texture(ctx: &Context, path: &str) -> Result<eframe::egui::TextureHandle, String> {
-    let img = image::open(path).map_err(|e| format!("Failed to open image: {}", e))?;
-    let img = img.to_rgba8();
-    let (width, height) = img.dimensions();
-    if width != 128 || height != 128 {
-        return Err(format!(
-            "Image dimensions do not match the expected size: got {}x{}, expected 128x128.",
-            width, height
-        ));
+// ---------- MAIN FUNCTION ----------
+
+// The one place logging is set up. Nothing else installs a subscriber, and
+// try_init leaves an existing one in place instead of panicking.
+fn init_logging() {
+    let result = tracing_subscriber::FmtSubscriber::builder()
+        .with_env_filter(EnvFilter::from_default_env().add_directive(Level::INFO.into()))
+        .with_thread_ids(true)
+        .with_thread_names(true)
+        .with_file(true)
+        .with_line_number(true)
+        .with_target(false)
+        .compact()
+        .try_init();
+    if let Err(e) = result {
+        tracing::debug!("Keeping the existing tracing subscriber: {}", e);
     }
-    let color_image = ColorImage::from_rgba_unmultiplied(
-        [width as usize, height as usize],
-        &img.into_raw(),
-    );
-    Ok(ctx.load_texture("Tzolk'in Glyph", color_image, TextureOptions::default()))
-}
-
-// A function to map Haab names to their respective image file paths.
-fn get_haab_glyphs() -> HashMap<&'static str, &'static str> {
-    let mut glyphs = HashMap::new();
-    glyphs.insert("Pop", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Pop.png");
-    glyphs.insert("Wo'", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Wo'.png");
-    glyphs.insert("Siq'", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Siq.png");
-    glyphs.insert("Soxj'", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Soxj'.png");
-    glyphs.insert("Sotj", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Sotj.png");
-    glyphs.insert("Xul", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Xul.png");
-    glyphs.insert("Yax'in", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Yax'in.png");
-    glyphs.insert("Mal", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Mal.png");
-    glyphs.insert("Chen", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Chen.png");
-    glyphs.insert("Yax", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Yax.png");
-    glyphs.insert("Sax", "C:/users/phine/ddocuments/github/mayan_calendar/src/haab/glyphs/Sax.png");
-    glyphs.insert("Skoh", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Skoh.png");
-    glyphs.insert("Mal", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Mal.png");
-    glyphs.insert("Kanx'in", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Kanx'in.png");
-    glyphs.insert("Muwan", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Muwan.png");
-    glyphs.insert("Pax", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Pax.png");
-    glyphs.insert("Kayab", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Kayab.png");
-    glyphs.insert("Kunx'u", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Kunx'u.png");
-    glyphs.insert("Wayeb", "C:/users/phine/documents/github/mayan_calendar/src/haab/glyphs/Wayeb.png");
-    glyphs
 }
 
-// A function to load Haab names as texture from image
-fn load_haab_image_as_texture(ctx: &Context, path: &str) -> Result<eframe::egui::TextureHandle, String> {
-    let img = image::open(path).map_err(|e| format!("Failed to open image: {}", e))?;
-    let img = img.to_rgba8();
-    let (width, height) = img.dimensions();
-    if width != 128 || height != 128 {
-        return Err(format!(
-            "Image dimensions do not match the expected size: got {}x{}, expected 128x128.",
-            width, height
-        ));
+fn main() {
+    init_logging();
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    if let Err(problems) = config.validate() {
+        for problem in problems {
+            tracing::warn!("Glyph config: {}", problem);
+        }
     }
-    let color_image = ColorImage::from_rgba_unmultiplied(
-        [width as usize, height as usize],
-        &img.into_raw(),
-    );
-    Ok(ctx.load_texture("Haab Glyph", color_image, TextureOptions::default()))
-}
-
-fn ui_example(ui: &mut Ui, ctx: &Context) {
-  let now = Utc::now().date_naive();
-  let year = now.year();
-  let month = now.month() as i32;
-  let day = now.day() as i32;
-
-  let jdn = gregorian_to_jdn(year, month, day);
-  let days_since_creation = jdn - 584283;
-
-  // Long Count Calculation
-  let (baktun, katun, tun, uinal, kin) = long_count(days_since_creation);
-
-  // Tzolk'in and Haab' Calendar Calculations
-  let tzolkin = tzolkin_date(days_since_creation);
-  let haab = haab_date(days_since_creation);
-
-  // Additional Info
-  let moon = moon_phase(jdn);
-  let bearer = year_bearer(jdn);
-  let venus = venus_phase(jdn);
-  let (solstice, days_until) = next_solstice_or_equinox(year, month, day);
-  let eclipse = next_eclipse(jdn);
-
-  // Historical Event Lookup
-  let historical = historical_event(jdn);
-
-  // UI Layout
-  ui.vertical(|ui| {
-      ui.heading("Mayan Date:");
-
-      // Gregorian Date
-      ui.label(format!("📅 Gregorian Date: {}-{:02}-{:02}", year, month, day));
-
-      // Long Count
-      ui.label(format!("🔢 Long Count: {}.{}.{}.{}.{}", baktun, katun, tun, uinal, kin));
-
-      // Long Count Mayan Unicode Glyphs
-      ui.label(format!(
-          "📜 Long Count (Unicode): {}{}{}{}{}",
-          mayan_numeral(baktun),
-          mayan_numeral(katun),
-          mayan_numeral(tun),
-          mayan_numeral(uinal),
-          mayan_numeral(kin)
-      ));
-
-      ui.label("📜 Long Count (ASCII):");
-      ui.monospace(format!("Baktun:\n{}", mayan_ascii_number(baktun)));
-      ui.monospace(format!("Katun:\n{}", mayan_ascii_number(katun)));
-      ui.monospace(format!("Tun:\n{}", mayan_ascii_number(tun)));
-      ui.monospace(format!("Uinal:\n{}", mayan_ascii_number(uinal)));
-      ui.monospace(format!("Kin:\n{}", mayan_ascii_number(kin)));
-     
-      ui.label("📜 Long Count (Unicode):");
-      ui.label(format!(
-          "{} {} {} {} {}",
-          mayan_numeral(baktun),
-          mayan_numeral(katun),
-          mayan_numeral(tun),
-          mayan_numeral(uinal),
-          mayan_numeral(kin)
-      ));
-      
-      // Tzolk'in and Haab' Dates
-      ui.label(format!(
-          "🌞 Tzolk'in Date: {} {}",
-          tzolkin.number, tzolkin.yucatec_name
-      ));
-      ui.label(format!(
-          "🌙 Haab' Date: {} {}",
-          haab.day, haab.yucatec_month
-      ));
-
-      // Year Bearer
-      ui.label(format!("🌞 Year Bearer: {}", bearer));
-
-      // Moon Phase
-      ui.label(format!("🌕 Moon Phase: {}", moon));
-
-      // Venus Cycle Phase
-      ui.label(format!("✨ Venus Cycle: {}", venus));
-
-      // Solstices/Equinoxes
-      ui.label(format!(
-          "🌓 Next Solstice/Equinox: {} ({} days away)",
-          solstice, days_until
-      ));
-
-      // Eclipse Prediction
-      ui.label(format!("🌘 Eclipse Prediction: {}", eclipse));
-
-      // Historical Events
-      if let Some(event) = historical {
-          ui.label(format!("🏛️ Historical Event Today: {}", event));
-      } else {
-          ui.label("📜 No significant historical event today.");
-      }
-
-      // Add Glyph Images for Tzolk'in and Haab'
-      ui.add_space(8.0);
-      ui.horizontal(|ui| {
-          let tzolkin_glyphs = get_tzolkin_glyphs();
-          if let Some(image_path) = tzolkin_glyphs.get(tzolkin.yucatec_name) {
-              match load_tzolkin_image_as_texture(ctx, image_path) {
-                  Ok(texture) => {
-                      ui.image(&texture);
-                  }
-                  Err(err) => {
-                      ui.label(format!("❌ Failed to load Tzolk'in glyph: {}", err));
-                  }
-              }
-          }
-
-          ui.add_space(16.0);
-
-          let haab_glyphs = get_haab_glyphs();
-          if let Some(image_path) = haab_glyphs.get(haab.yucatec_month) {
-              match load_haab_image_as_texture(ctx, image_path) {
-                  Ok(texture) => {
-                      ui.image(&texture);
-                  }
-                  Err(err) => {
-                      ui.label(format!("❌ Failed to load Haab' glyph: {}", err));
-                  }
-              }
-          } else {
-              ui.label("❌ No Haab' glyph found!");
-          }
-      });
-  });
-}
-
-struct MyApp {
-    current_time: chrono::NaiveTime,
-}
-
-impl App for MyApp {
-    fn new() -> Self {
-        Self {
-            current_time: chrono::Local::now().time(),
+    let correlation = config.correlation.jdn_offset();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let request = match parse_args(&args, correlation) {
+        Ok(request) => request,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            std::process::exit(2);
+        }
+    };
+    // Any arguments mean a report on the terminal, for today unless a date was given
+    if let Some(request) = request {
+        if let Some(input) = &request.csv {
+            if let Err(e) = run_batch(input, request.out.as_ref(), correlation, request.calendar) {
+                eprintln!("Batch conversion failed: {}", e);
+                std::process::exit(1);
+            }
+            return;
         }
+        let options = ReportOptions { color: request.color.enabled(), haab_numbering: config.haab_numbering };
+        if request.watch {
+            watch(correlation, config.timezone, request.format, options);
+        }
+        let date = request.date.unwrap_or_else(|| config.timezone.now().date());
+        if request.tzolkin_table {
+            let days = gregorian_to_jdn(date.year(), date.month() as i32, date.day() as i32) - correlation;
+            print!("{}", tzolkin_table_text(&tzolkin_date(days)));
+            return;
+        }
+        print!("{}", text_report_with(date, correlation, request.format, options));
+        return;
     }
-}
-
-    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
-        CentralPanel::default().show(ctx, |ui| {
-            // Add scrolling capability
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                ui_example(ui, ctx);
-            });
-        });
+    // Run the application, falling back to a text report if no window can be opened
+    #[cfg(feature = "gui")]
+    match gui::run(gui::DisplayConfig::default()) {
+        Ok(()) => return,
+        Err(e) => tracing::error!("Failed to launch app: {}", e),
     }
+    let today = config.timezone.now().date();
+    let options = ReportOptions { color: ColorChoice::Auto.enabled(), haab_numbering: config.haab_numbering };
+    print!("{}", text_report_with(today, correlation, ReportFormat::Plain, options));
 }
 
-fn configure_fonts(ctx: &egui::Context) {
-    use eframe::egui::{FontDefinitions, FontFamily, FontData};
-    use std::sync::Arc;
-
-    let mut fonts = FontDefinitions::default();
-
-    // Add a fallback font that supports Mayan Unicode (e.g., Noto Sans Symbols)
-    fonts.font_data.insert(
-        "NotoSansSymbols".to_string(),
-        Arc::new(FontData::from_static(include_bytes!(
-            "C:/Users/phine/Documents/GitHub/mayan_calendar/src/fonts/NotoSansMayanNumerals-Regular.ttf" // Update to the path of your font file
-        ))),
-    );
-
-    // Use the new font for both Proportional and Monospace families
-    fonts
-        .families
-        .entry(FontFamily::Proportional)
-        .or_default()
-        .insert(0, "NotoSansSymbols".to_string());
-    fonts
-        .families
-        .entry(FontFamily::Monospace)
-        .or_default()
-        .insert(0, "NotoSansSymbols".to_string());
-
-    ctx.set_fonts(fonts);
-}
-
-fn main() {
-    let options = eframe::NativeOptions::default();
-    eframe::run_native(
-        "Mayan Calendar",
-        options,
-        Box::new(|cc| {
-            configure_fonts(&cc.egui_ctx); // Configure custom fonts
-            Ok(Box::new(MyApp))
-        }),
-    )
-    .unwrap_or_else(|e| {
-        eprintln!("Failed to launch app: {}", e);
-
-        let now = Utc::now().date_naive();
-        let year = now.year();
-        let month = now.month() as i32;
-        let day = now.day() as i32;
-
-        // Convert the current Gregorian date to JDN.
-        let jdn = gregorian_to_jdn(year, month, day);
-        // GMT correlation: 0.0.0.0.0 corresponds to JDN 584283.
-        let days_since_creation = jdn - 584283;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // Compute Long Count.
-        let (baktun, katun, tun, uinal, kin) = long_count(days_since_creation);
-
-        let tzolkin = tzolkin_date(days_since_creation);
-        let haab = haab_date(days_since_creation);
-
-        let bearer = year_bearer(jdn);
-        let moon = moon_phase(jdn);
-        let venus = venus_phase(jdn);
-        let (solstice, days_until) = next_solstice_or_equinox(year, month, day);
-        let eclipse = next_eclipse(jdn);
-        let _historical = historical_events(year, month, day);
-
-        // ✅ Fix: Declare glyphs before using it!
-        let glyphs = long_count_glyphs();
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
 
-        println!("📆 Gregorian Date: {}-{:02}-{:02}", year, month, day);
-        println!("🔢 Julian Day Number: {}", jdn);
-        println!("🕰 Days since 0.0.0.0.0: {}", days_since_creation);
-        println!(
-            "🌞 Tzolk'in Date: {} {} (K’iche’: {})",
-            tzolkin.number, tzolkin.yucatec_name, tzolkin.kiche_name
+    #[test]
+    fn test_parse_args() {
+        let date = |format| {
+            Ok(Some(ReportRequest { date: NaiveDate::from_ymd_opt(2012, 12, 21), format, ..Default::default() }))
+        };
+        assert_eq!(parse_args(&args(&[]), 584283), Ok(None));
+        assert_eq!(parse_args(&args(&["--date", "2012-12-21"]), 584283), date(ReportFormat::Plain));
+        assert_eq!(parse_args(&args(&["--longcount", "13.0.0.0.0"]), 584283), date(ReportFormat::Plain));
+        assert_eq!(
+            parse_args(&args(&["--date", "2012-12-21", "--format", "markdown"]), 584283),
+            date(ReportFormat::Markdown)
         );
-        println!(
-            "🌙 Haab’ Date: {} {} (K’iche’: {})",
-            haab.day, haab.yucatec_month, haab.kiche_month
+        assert_eq!(
+            parse_args(&args(&["--format", "md"]), 584283),
+            Ok(Some(ReportRequest { format: ReportFormat::Markdown, ..Default::default() }))
         );
-        println!("🌞 Year Bearer: {}", bearer);
-        println!("🌙 Moon Phase: {}", moon);
-        println!("✨ Venus Cycle: {}", venus);
-        println!(
-            "🌓 Next Solstice/Equinox: {} ({} days away)",
-            solstice, days_until
+        assert_eq!(
+            parse_args(&args(&["--watch", "--format", "plain"]), 584283),
+            Ok(Some(ReportRequest { watch: true, ..Default::default() }))
         );
-        println!("🌘 Eclipse Prediction: {}", eclipse);
-        println!("\n📜 Long Count (ASCII):");
-        println!("Baktun:\n{}", mayan_ascii_number(baktun));
-        println!("Katun:\n{}", mayan_ascii_number(katun));
-        println!("Tun:\n{}", mayan_ascii_number(tun));
-        println!("Uinal:\n{}", mayan_ascii_number(uinal));
-        println!("Kin:\n{}", mayan_ascii_number(kin));
-        println!(
-            "📜 Long Count: {}.{}.{}.{}.{}  {}{}{}{}{} {}{}{}{}{}",
-            baktun,
-            katun,
-            tun,
-            uinal,
-            kin,
-            glyphs[&baktun],
-            glyphs[&katun],
-            glyphs[&tun],
-            glyphs[&uinal],
-            glyphs[&kin],
-            mayan_numeral(baktun),
-            mayan_numeral(katun),
-            mayan_numeral(tun),
-            mayan_numeral(uinal),
-            mayan_numeral(kin)
+        assert_eq!(
+            parse_args(&args(&["--color", "always"]), 584283).map(|r| r.unwrap().color),
+            Ok(ColorChoice::Always)
         );
+        assert!(parse_args(&args(&["--color", "sometimes"]), 584283).is_err());
+        assert_eq!(
+            parse_args(&args(&["--csv", "in.csv", "--out", "out.csv"]), 584283).map(|r| r.unwrap().out),
+            Ok(Some(PathBuf::from("out.csv")))
+        );
+        assert!(parse_args(&args(&["--out", "out.csv"]), 584283).is_err());
+        assert!(parse_args(&args(&["--watch", "--date", "2012-12-21"]), 584283).is_err());
+        assert_eq!(
+            parse_args(&args(&["--tzolkin-table", "--date", "2012-12-21"]), 584283).map(|r| r.unwrap().tzolkin_table),
+            Ok(true)
+        );
+        assert!(parse_args(&args(&["--watch", "--tzolkin-table"]), 584283).is_err());
+        assert!(parse_args(&args(&["--format", "html"]), 584283).is_err());
+        assert!(parse_args(&args(&["--date", "2012-13-01"]), 584283).is_err());
+        assert!(parse_args(&args(&["--longcount", "9.18"]), 584283).is_err());
+        assert!(parse_args(&args(&["--date"]), 584283).is_err());
+
+        // Spanish records before October 1582 are Julian, ten days behind by 1511
+        let first_contact = |list: &[&str]| parse_args(&args(list), 584283).map(|r| r.unwrap().date);
+        assert_eq!(
+            first_contact(&["--date", "1511-08-01", "--calendar", "historical"]),
+            Ok(NaiveDate::from_ymd_opt(1511, 8, 11))
+        );
+        assert_eq!(
+            first_contact(&["--calendar", "julian", "--date", "1511-08-01"]),
+            Ok(NaiveDate::from_ymd_opt(1511, 8, 11))
+        );
+        assert_eq!(first_contact(&["--date", "1511-08-01"]), Ok(NaiveDate::from_ymd_opt(1511, 8, 1)));
+        assert!(parse_args(&args(&["--date", "1582-10-10", "--calendar", "historical"]), 584283).is_err());
+        assert!(parse_args(&args(&["--calendar", "mayan"]), 584283).is_err());
+    }
 
-        println!("Baktun:\n{}", mayan_ascii_number(baktun));
-        println!("Katun:\n{}", mayan_ascii_number(katun));
-        println!("Tun:\n{}", mayan_ascii_number(tun));
-        println!("Uinal:\n{}", mayan_ascii_number(uinal));
-        println!("Kin:\n{}", mayan_ascii_number(kin));
-
-        let _ = tzolkin_glyphs();
-        let _ = haab_glyphs();
-        let _ = historical_glyphs();
+    #[test]
+    fn test_init_logging_twice() {
+        init_logging();
+        init_logging();
+    }
 
-        if let Some(event) = historical_event(jdn) {
-            let event_glyphs = historical_glyphs();
-            println!(
-                "🏛️ Historical Event Today: {} {}",
-                event,
-                event_glyphs.get(event).unwrap_or(&"❓")
-            );
-        }
-    });
+    #[test]
+    fn test_watch_frame() {
+        let now = NaiveDate::from_ymd_opt(2012, 12, 21).unwrap().and_hms_opt(9, 30, 5).unwrap();
+        let frame = watch_frame(now, "📆 Gregorian Date: 2012-12-21\n");
+        assert_eq!(frame, "\x1b[2J\x1b[H🕰 Local Time: 2012-12-21 09:30:05\n📆 Gregorian Date: 2012-12-21\n");
+    }
 }
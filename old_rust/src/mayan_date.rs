@@ -0,0 +1,68 @@
+use crate::date_utils::{
+    count_819, haab_date, lord_of_the_night, tzolkin_date, CalendarRound, HaabDate, Station819, TzolkinDate,
+};
+use crate::long_count::LongCount;
+
+/// Every cycle position for a single day, with no display concerns.
+/// The day count is from 0.0.0.0.0, so no correlation is needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MayanDate {
+    pub long_count: LongCount,
+    pub tzolkin: TzolkinDate,
+    pub haab: HaabDate,
+    /// Lord of the Night, 1-9 for G1-G9
+    pub lord_of_night: i32,
+    pub count_819: Station819,
+    pub calendar_round: CalendarRound,
+}
+
+impl MayanDate {
+    pub fn from_days(days: i32) -> Self {
+        Self {
+            long_count: LongCount::from_days(days),
+            tzolkin: tzolkin_date(days),
+            haab: haab_date(days),
+            lord_of_night: lord_of_the_night(days),
+            count_819: count_819(days).into(),
+            calendar_round: CalendarRound::from_days(days),
+        }
+    }
+
+    /// Days since 0.0.0.0.0
+    pub fn days(&self) -> i32 {
+        self.long_count.to_days()
+    }
+}
+
+impl From<LongCount> for MayanDate {
+    fn from(long_count: LongCount) -> Self {
+        Self::from_days(long_count.to_days())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date_utils::{Color, Direction};
+
+    #[test]
+    fn test_mayan_date_end_of_13th_baktun() {
+        let date = MayanDate::from(LongCount::new(13, 0, 0, 0, 0));
+        assert_eq!(date.days(), 1872000);
+        assert_eq!(date.tzolkin, TzolkinDate::new(4, "Ajaw"));
+        assert_eq!(date.haab, HaabDate::new(3, "K'ank'in"));
+        assert_eq!(date.lord_of_night, 9);
+        assert_eq!(date.calendar_round.position(), Some(1872000 % 18980));
+        assert_eq!(date.count_819, count_819(1872000).into());
+        assert_eq!(MayanDate::from_days(date.days()), date);
+    }
+
+    #[test]
+    fn test_mayan_date_creation() {
+        let creation = MayanDate::from_days(0);
+        assert_eq!(creation.long_count, LongCount::new(0, 0, 0, 0, 0));
+        assert_eq!(creation.calendar_round, CalendarRound::from_days(18980));
+        assert_eq!(creation.count_819, Station819 { day: 3, direction: Direction::East, color: Color::Red });
+    }
+}
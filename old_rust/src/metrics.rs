@@ -0,0 +1,72 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// std's Instant panics in the browser, so time with web-time there
+#[cfg(target_arch = "wasm32")]
+pub use web_time::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::Instant;
+
+// Performance Metrics
+#[derive(Default)]
+pub struct Metrics {
+    calculation_time: AtomicU64,
+    glyph_load_time: AtomicU64,
+    render_time: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_calculation(&self, duration: std::time::Duration) {
+        self.calculation_time.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_glyph_load(&self, duration: std::time::Duration) {
+        self.glyph_load_time.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_render(&self, duration: std::time::Duration) {
+        self.render_time.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn report(&self) -> String {
+        format!(
+            "Performance Metrics:\n\
+             Calculation Time: {}µs\n\
+             Glyph Load Time: {}µs\n\
+             Render Time: {}µs\n\
+             Cache Hits: {}\n\
+             Cache Misses: {}\n\
+             Cache Hit Rate: {:.2}%",
+            self.calculation_time.load(Ordering::Relaxed),
+            self.glyph_load_time.load(Ordering::Relaxed),
+            self.render_time.load(Ordering::Relaxed),
+            self.cache_hits.load(Ordering::Relaxed),
+            self.cache_misses.load(Ordering::Relaxed),
+            self.cache_hit_rate() * 100.0
+        )
+    }
+
+    fn cache_hit_rate(&self) -> f64 {
+        let hits = self.cache_hits.load(Ordering::Relaxed) as f64;
+        let misses = self.cache_misses.load(Ordering::Relaxed) as f64;
+        let total = hits + misses;
+        if total > 0.0 {
+            hits / total
+        } else {
+            0.0
+        }
+    }
+}
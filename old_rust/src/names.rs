@@ -0,0 +1,144 @@
+use crate::date_utils::{HAAB_MONTHS, TZOLKIN_NAMES};
+
+/// Orthographies the day and month names can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NameSet {
+    /// Modern Yucatec spelling (1989 ALMG alphabet), as used throughout the crate
+    #[default]
+    Yucatec,
+    /// Highland K'iche' day names
+    Kiche,
+    /// The older Spanish-derived spellings common in English literature
+    Classic,
+}
+
+/// K'iche' Tzolk'in day names, in the same order as the Yucatec ones
+pub const KICHE_DAY_NAMES: [&str; 20] = [
+    "Imox", "Iq'", "Aq'ab'al", "K'at", "Kan",
+    "Kame", "Kej", "Q'anil", "Toj", "Tz'i'",
+    "B'atz'", "E", "Aj", "I'x", "Tz'ikin",
+    "Ajmaq", "No'j", "Tijax", "Kawoq", "Ajpu"
+];
+
+pub const CLASSIC_DAY_NAMES: [&str; 20] = [
+    "Imix", "Ik", "Akbal", "Kan", "Chicchan",
+    "Cimi", "Manik", "Lamat", "Muluc", "Oc",
+    "Chuen", "Eb", "Ben", "Ix", "Men",
+    "Cib", "Caban", "Etznab", "Cauac", "Ahau"
+];
+
+pub const CLASSIC_MONTH_NAMES: [&str; 19] = [
+    "Pop", "Uo", "Zip", "Zotz", "Tzec", "Xul", "Yaxkin", "Mol",
+    "Chen", "Yax", "Zac", "Ceh", "Mac", "Kankin", "Muan", "Pax",
+    "Kayab", "Cumku", "Uayeb"
+];
+
+/// Divinatory meaning of each day in the K'iche' day-keepers' tradition, in
+/// the same order as the names, after Barbara Tedlock, *Time and the Highland
+/// Maya* (1992), ch. 5
+pub const DAY_MEANINGS: [&str; 20] = [
+    "primordial water, beginnings, the left hand",
+    "wind, breath and life",
+    "dawn and dusk, darkness before light",
+    "the net: entanglement and gathering",
+    "the serpent: strength and vital energy",
+    "death and the ancestors",
+    "the deer: the four directions and leadership",
+    "the seed: ripening and abundance",
+    "offering and payment of debts",
+    "the dog: law and justice",
+    "the thread: time and the arts",
+    "the road: journeys and destiny",
+    "the cane: home and household",
+    "the jaguar: the earth and its shrines",
+    "the bird: good fortune and vision",
+    "forgiveness and the dead",
+    "thought, knowledge and wisdom",
+    "the flint: cutting and healing",
+    "the storm: community and the family",
+    "the sun and the hunter: heroism",
+];
+
+/// Meaning of the Tzolk'in day at `name_index` (0 = Imix ... 19 = Ajaw),
+/// taken mod 20
+pub fn tzolkin_meaning(name_index: i32) -> &'static str {
+    DAY_MEANINGS[name_index.rem_euclid(20) as usize]
+}
+
+/// Tzolk'in day name for `index` (0 = Imix ... 19 = Ajaw)
+pub fn day_name(index: usize, set: NameSet) -> Option<&'static str> {
+    let names = match set {
+        NameSet::Yucatec => &TZOLKIN_NAMES,
+        NameSet::Kiche => &KICHE_DAY_NAMES,
+        NameSet::Classic => &CLASSIC_DAY_NAMES,
+    };
+    names.get(index).copied()
+}
+
+/// Haab' month name for `index` (0 = Pop ... 18 = Wayeb')
+pub fn month_name(index: usize, set: NameSet) -> Option<&'static str> {
+    let names = match set {
+        // Highland month names vary from town to town, so K'iche' keeps the Yucatec months
+        NameSet::Yucatec | NameSet::Kiche => &HAAB_MONTHS,
+        NameSet::Classic => &CLASSIC_MONTH_NAMES,
+    };
+    names.get(index).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_name_sets_are_complete() {
+        for set in [NameSet::Yucatec, NameSet::Kiche, NameSet::Classic] {
+            let days: HashSet<_> = (0..20).map(|i| day_name(i, set).unwrap()).collect();
+            let months: HashSet<_> = (0..19).map(|i| month_name(i, set).unwrap()).collect();
+            assert_eq!(days.len(), 20, "{:?}", set);
+            assert_eq!(months.len(), 19, "{:?}", set);
+            assert!(days.iter().chain(&months).all(|name| !name.is_empty()));
+            assert_eq!(day_name(20, set), None);
+            assert_eq!(month_name(19, set), None);
+        }
+    }
+
+    #[test]
+    fn test_tzolkin_meaning() {
+        assert_eq!(tzolkin_meaning(0), "primordial water, beginnings, the left hand");
+        assert_eq!(tzolkin_meaning(19), "the sun and the hunter: heroism");
+        assert_eq!(tzolkin_meaning(20), tzolkin_meaning(0));
+        assert_eq!(tzolkin_meaning(-1), tzolkin_meaning(19));
+        assert_eq!(DAY_MEANINGS.iter().collect::<HashSet<_>>().len(), 20);
+    }
+
+    #[test]
+    fn test_name_sets_line_up() {
+        let days = [
+            (0, "Imix", "Imox", "Imix"),
+            (1, "Ik'", "Iq'", "Ik"),
+            (7, "Lamat", "Q'anil", "Lamat"),
+            (15, "Kib'", "Ajmaq", "Cib"),
+            (19, "Ajaw", "Ajpu", "Ahau"),
+        ];
+        for (index, yucatec, kiche, classic) in days {
+            assert_eq!(day_name(index, NameSet::Yucatec), Some(yucatec));
+            assert_eq!(day_name(index, NameSet::Kiche), Some(kiche));
+            assert_eq!(day_name(index, NameSet::Classic), Some(classic));
+        }
+        let months = [
+            (0, "Pop", "Pop"),
+            (6, "Yaxk'in", "Yaxkin"),
+            (10, "Sak'", "Zac"),
+            (13, "K'ank'in", "Kankin"),
+            (17, "Kumk'u", "Cumku"),
+            (18, "Wayeb'", "Uayeb"),
+        ];
+        for (index, yucatec, classic) in months {
+            assert_eq!(month_name(index, NameSet::Yucatec), Some(yucatec));
+            assert_eq!(month_name(index, NameSet::Kiche), Some(yucatec));
+            assert_eq!(month_name(index, NameSet::Classic), Some(classic));
+        }
+    }
+}
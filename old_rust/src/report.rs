@@ -0,0 +1,328 @@
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::calendar::CalendarData;
+use crate::date_utils::{haab_date_numbered, tzolkin_table, HaabNumbering, TzolkinDate, TZOLKIN_NAMES};
+use crate::glyphs::{long_count_glyphs, mayan_ascii_number, mayan_numeral};
+use crate::long_count::{creation_cycle, cycle_string, next_baktun_ending, next_period_ending};
+use crate::names::tzolkin_meaning;
+
+/// How `text_report` lays out the report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    /// The emoji-labelled lines with ASCII-art numerals
+    #[default]
+    Plain,
+    /// A two-column table
+    Markdown,
+    /// The serialized `CalendarData`
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error("Unknown report format '{0}' (expected plain, markdown or json)")]
+pub struct UnknownFormat(pub String);
+
+impl FromStr for ReportFormat {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "plain" | "text" => Ok(ReportFormat::Plain),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            #[cfg(feature = "serde")]
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+// ANSI SGR codes for each section of the plain report
+const LONG_COUNT_COLOR: &str = "33";
+const TZOLKIN_COLOR: &str = "32";
+const HAAB_COLOR: &str = "36";
+const EVENT_COLOR: &str = "1;35";
+
+// "1 day", "2 days"
+fn day_count(days: i32) -> String {
+    if days == 1 {
+        "1 day".to_string()
+    } else {
+        format!("{} days", days)
+    }
+}
+
+// Wrap `text` in an ANSI color, or leave it alone when color is off
+fn paint(text: &str, code: &str, color: bool) -> String {
+    if color {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// How `text_report_with` renders the report beyond its format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReportOptions {
+    /// Highlight the Long Count, Tzolk'in, Haab' and historical-event lines
+    /// of the plain format with ANSI escapes. Markdown and JSON are never colored.
+    pub color: bool,
+    /// Numbering for the plain and Markdown Haab' dates; JSON always holds
+    /// the seating numbering
+    pub haab_numbering: HaabNumbering,
+}
+
+/// The full report for a date; `correlation` is the JDN of 0.0.0.0.0
+pub fn text_report(date: NaiveDate, correlation: i32, format: ReportFormat) -> String {
+    text_report_with(date, correlation, format, ReportOptions::default())
+}
+
+/// Like `text_report`, rendered with `options`
+pub fn text_report_with(date: NaiveDate, correlation: i32, format: ReportFormat, options: ReportOptions) -> String {
+    let data = CalendarData::new(date.and_time(NaiveTime::MIN), correlation);
+    let mut report = String::new();
+    match format {
+        ReportFormat::Plain => write_report(&mut report, &data, options),
+        ReportFormat::Markdown => write_markdown(&mut report, &data, options.haab_numbering),
+        #[cfg(feature = "serde")]
+        ReportFormat::Json => {
+            report = serde_json::to_string_pretty(&data).expect("CalendarData always serializes");
+            report.push('\n');
+            Ok(())
+        }
+    }
+    .expect("writing to a String cannot fail");
+    report
+}
+
+/// The 260-day count as a 20-row grid, one row per day name and one column
+/// per run of 20 days, with `today` in brackets
+pub fn tzolkin_table_text(today: &TzolkinDate) -> String {
+    let table = tzolkin_table();
+    let mut out = String::new();
+    for (j, name) in TZOLKIN_NAMES.iter().enumerate() {
+        out.push_str(&format!("{:<9}", name));
+        for column in &table {
+            let day = &column[j];
+            if day == today {
+                out.push_str(&format!("[{:>2}]", day.number));
+            } else {
+                out.push_str(&format!(" {:>2} ", day.number));
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// Markdown table of the same fields as the plain report
+fn write_markdown(out: &mut String, data: &CalendarData, haab_numbering: HaabNumbering) -> fmt::Result {
+    let lunar = data.lunar_series;
+    let mut rows = vec![
+        ("Gregorian Date", data.gregorian_date.to_string()),
+        ("Julian Day Number", data.julian_day_number.to_string()),
+        ("Days since 0.0.0.0.0", data.days_since_creation.to_string()),
+        ("Long Count", data.mayan.long_count.to_string()),
+    ];
+    if creation_cycle(data.days_since_creation) > 0 {
+        rows.push(("Creation Cycle", cycle_string(data.days_since_creation)));
+    }
+    let (katun_ending, days_to_katun) = next_period_ending(data.days_since_creation);
+    rows.push(("Next K'atun Ending", format!("{} in {}", katun_ending, day_count(days_to_katun))));
+    let (baktun_ending, days_to_baktun) = next_baktun_ending(data.days_since_creation);
+    rows.push(("Next Bak'tun Ending", format!("{} in {}", baktun_ending, day_count(days_to_baktun))));
+    rows.push(("Tzolk'in Date", data.mayan.tzolkin.to_string()));
+    if let Some(index) = data.mayan.tzolkin.veintena_position() {
+        rows.push(("Day Meaning", tzolkin_meaning(index).to_string()));
+    }
+    rows.extend([
+        ("Haab' Date", haab_date_numbered(data.days_since_creation, haab_numbering).to_string()),
+        ("Year Bearer", data.year_bearer.clone()),
+        ("Moon Phase", data.moon_phase.to_string()),
+        (
+            "Lunar Series",
+            format!(
+                "Moon age {} days, lunation {} of 6, {}-day month",
+                lunar.age, lunar.lunation, lunar.month_length
+            ),
+        ),
+        ("Venus Cycle", format!("{} (Day {} of 584)", data.venus_phase, data.venus_cycle_day + 1)),
+        (
+            "Next Solstice/Equinox",
+            format!("{} ({} days away)", data.next_solstice.name, data.next_solstice.days_until),
+        ),
+        ("Eclipse Prediction", data.eclipse_status.to_string()),
+    ]);
+    if let Some(event) = &data.historical_event {
+        rows.push(("Historical Event", event.clone()));
+    }
+
+    writeln!(out, "| Field | Value |")?;
+    writeln!(out, "| --- | --- |")?;
+    for (field, value) in rows {
+        // Pipes would end the cell early
+        writeln!(out, "| {} | {} |", field, value.replace('|', "\\|"))?;
+    }
+    Ok(())
+}
+
+fn write_report(out: &mut String, data: &CalendarData, options: ReportOptions) -> fmt::Result {
+    let ReportOptions { color, haab_numbering } = options;
+    let glyphs = long_count_glyphs();
+    let lc = &data.mayan.long_count;
+    let tzolkin = format!("🌞 Tzolk'in Date: {}", data.mayan.tzolkin);
+    let haab = format!("🌙 Haab' Date: {}", haab_date_numbered(data.days_since_creation, haab_numbering));
+
+    writeln!(out, "📆 Gregorian Date: {}", data.gregorian_date)?;
+    writeln!(out, "🔢 Julian Day Number: {}", data.julian_day_number)?;
+    writeln!(out, "🕰 Days since 0.0.0.0.0: {}", data.days_since_creation)?;
+    writeln!(out, "{}", paint(&tzolkin, TZOLKIN_COLOR, color))?;
+    if let Some(index) = data.mayan.tzolkin.veintena_position() {
+        writeln!(out, "🔮 Day Meaning: {}", tzolkin_meaning(index))?;
+    }
+    writeln!(out, "{}", paint(&haab, HAAB_COLOR, color))?;
+    writeln!(out, "🌞 Year Bearer: {}", data.year_bearer)?;
+    writeln!(out, "🌙 Moon Phase: {}", data.moon_phase)?;
+    let lunar = data.lunar_series;
+    writeln!(
+        out,
+        "🌙 Lunar Series: Moon age {} days, lunation {} of 6, {}-day month",
+        lunar.age, lunar.lunation, lunar.month_length
+    )?;
+    writeln!(out, "✨ Venus Cycle: {} (Day {} of 584)", data.venus_phase, data.venus_cycle_day + 1)?;
+    writeln!(
+        out,
+        "🌓 Next Solstice/Equinox: {} ({} days away)",
+        data.next_solstice.name, data.next_solstice.days_until
+    )?;
+    writeln!(out, "🌘 Eclipse Prediction: {}", data.eclipse_status)?;
+
+    let units = [
+        ("Baktun", lc.baktun),
+        ("Katun", lc.katun),
+        ("Tun", lc.tun),
+        ("Uinal", lc.uinal),
+        ("Kin", lc.kin),
+    ];
+    let pictographs: String = units
+        .iter()
+        .map(|(_, n)| *glyphs.get(n).unwrap_or(&"❓"))
+        .collect();
+    let numerals: String = units.iter().map(|(_, n)| mayan_numeral(*n)).collect();
+    let long_count = format!("📜 Long Count: {}  {} {}", lc, pictographs, numerals);
+    writeln!(out, "{}", paint(&long_count, LONG_COUNT_COLOR, color))?;
+    // Past 13.0.0.0.0 the count is clearer read within the new cycle
+    if creation_cycle(data.days_since_creation) > 0 {
+        writeln!(out, "🌀 Creation Cycle: {}", cycle_string(data.days_since_creation))?;
+    }
+    let (katun_ending, days_to_katun) = next_period_ending(data.days_since_creation);
+    writeln!(out, "⏳ Next K'atun Ending: {} in {}", katun_ending, day_count(days_to_katun))?;
+    let (baktun_ending, days_to_baktun) = next_baktun_ending(data.days_since_creation);
+    writeln!(out, "⏳ Next Bak'tun Ending: {} in {}", baktun_ending, day_count(days_to_baktun))?;
+    writeln!(out, "\n{}", paint("📜 Long Count (ASCII):", LONG_COUNT_COLOR, color))?;
+    for (unit, n) in units {
+        writeln!(out, "{}:\n{}", paint(unit, LONG_COUNT_COLOR, color), mayan_ascii_number(n))?;
+    }
+
+    if let Some(event) = &data.historical_event {
+        let event = format!("🏛️ Historical Event Today: {}", event);
+        writeln!(out, "{}", paint(&event, EVENT_COLOR, color))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_report() {
+        let report = text_report(NaiveDate::from_ymd_opt(2012, 12, 21).unwrap(), 584283, ReportFormat::Plain);
+        assert!(report.starts_with("📆 Gregorian Date: 2012-12-21\n"));
+        assert!(report.contains("🔢 Julian Day Number: 2456283\n"));
+        assert!(report.contains("📜 Long Count: 13.0.0.0.0 "));
+        assert!(report.contains("🌀 Creation Cycle: Cycle 1, 0.0.0.0.0\n"));
+        assert!(report.contains("⏳ Next K'atun Ending: 13.1.0.0.0 in 7200 days\n"));
+        assert!(report.contains("⏳ Next Bak'tun Ending: 14.0.0.0.0 in 144000 days\n"));
+        let eve = text_report(NaiveDate::from_ymd_opt(2012, 12, 20).unwrap(), 584283, ReportFormat::Plain);
+        assert!(eve.contains("⏳ Next K'atun Ending: 13.0.0.0.0 in 1 day\n"));
+        assert!(eve.contains("⏳ Next Bak'tun Ending: 13.0.0.0.0 in 1 day\n"));
+        assert!(report.contains("🌞 Tzolk'in Date: 4 Ajaw\n🔮 Day Meaning: the sun and the hunter: heroism\n"));
+        assert!(report.contains("🌙 Haab' Date: 3 K'ank'in\n"));
+        assert!(report.contains("🌙 Lunar Series: Moon age 8 days, lunation 5 of 6, 30-day month\n"));
+        assert!(report.contains("🌓 Next Solstice/Equinox: Spring Equinox (89 days away)\n"));
+    }
+
+    #[test]
+    fn test_colored_report() {
+        let date = NaiveDate::from_ymd_opt(2012, 12, 21).unwrap();
+        let color = ReportOptions { color: true, ..ReportOptions::default() };
+        let report = text_report_with(date, 584283, ReportFormat::Plain, color);
+        assert!(report.contains("\x1b[32m🌞 Tzolk'in Date: 4 Ajaw\x1b[0m\n"));
+        assert!(report.contains("\x1b[36m🌙 Haab' Date: 3 K'ank'in\x1b[0m\n"));
+        assert!(report.contains("\x1b[33m📜 Long Count: 13.0.0.0.0 "));
+        assert!(report.contains("\x1b[1;35m🏛️ Historical Event Today: 🌅 Completion of the 13th Bak'tun\x1b[0m\n"));
+        // Unstyled lines are untouched, and the other formats stay plain
+        assert!(report.starts_with("📆 Gregorian Date: 2012-12-21\n"));
+        assert!(!text_report_with(date, 584283, ReportFormat::Markdown, color).contains('\x1b'));
+        assert_eq!(
+            text_report_with(date, 584283, ReportFormat::Plain, ReportOptions::default()),
+            text_report(date, 584283, ReportFormat::Plain)
+        );
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let report = text_report(NaiveDate::from_ymd_opt(2012, 12, 21).unwrap(), 584283, ReportFormat::Markdown);
+        assert!(report.starts_with("| Field | Value |\n| --- | --- |\n| Gregorian Date | 2012-12-21 |\n"));
+        assert!(report.contains("| Long Count | 13.0.0.0.0 |\n"));
+        assert!(report.contains("| Creation Cycle | Cycle 1, 0.0.0.0.0 |\n"));
+        assert!(report.contains("| Next Bak'tun Ending | 14.0.0.0.0 in 144000 days |\n"));
+        let eve = text_report(NaiveDate::from_ymd_opt(2012, 12, 20).unwrap(), 584283, ReportFormat::Markdown);
+        assert!(eve.contains("| Next Bak'tun Ending | 13.0.0.0.0 in 1 day |\n"));
+        assert!(report.contains("| Day Meaning | the sun and the hunter: heroism |\n| Haab' Date | 3 K'ank'in |\n"));
+        assert!(report.contains("| Historical Event | 🌅 Completion of the 13th Bak'tun |\n"));
+        assert!(report.lines().all(|line| line.starts_with('|') && line.ends_with('|')));
+    }
+
+    #[test]
+    fn test_one_based_haab_report() {
+        let date = NaiveDate::from_ymd_opt(2012, 12, 21).unwrap();
+        let one_based = ReportOptions { haab_numbering: HaabNumbering::OneBased, ..ReportOptions::default() };
+        let plain = text_report_with(date, 584283, ReportFormat::Plain, one_based);
+        assert!(plain.contains("🌙 Haab' Date: 4 K'ank'in\n"));
+        let markdown = text_report_with(date, 584283, ReportFormat::Markdown, one_based);
+        assert!(markdown.contains("| Haab' Date | 4 K'ank'in |\n"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_report() {
+        let report = text_report(NaiveDate::from_ymd_opt(2012, 12, 21).unwrap(), 584283, ReportFormat::Json);
+        let data: CalendarData = serde_json::from_str(&report).unwrap();
+        assert_eq!(data.mayan.long_count.to_string(), "13.0.0.0.0");
+    }
+
+    #[test]
+    fn test_tzolkin_table_text() {
+        let text = tzolkin_table_text(&TzolkinDate::new(4, "Ajaw"));
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 20);
+        assert_eq!(lines[0], "Imix       1   8   2   9   3  10   4  11   5  12   6  13   7 ");
+        assert_eq!(lines[19], "Ajaw       7   1   8   2   9   3  10 [ 4] 11   5  12   6  13 ");
+        assert_eq!(text.matches('[').count(), 1);
+        // A date that can't be in the table highlights nothing
+        assert!(!tzolkin_table_text(&TzolkinDate::new(14, "Ajaw")).contains('['));
+    }
+
+    #[test]
+    fn test_report_format_from_str() {
+        assert_eq!("plain".parse(), Ok(ReportFormat::Plain));
+        assert_eq!("Markdown".parse(), Ok(ReportFormat::Markdown));
+        assert_eq!("md".parse(), Ok(ReportFormat::Markdown));
+        assert_eq!("html".parse::<ReportFormat>(), Err(UnknownFormat("html".to_string())));
+    }
+}